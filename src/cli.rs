@@ -0,0 +1,56 @@
+use std::fs;
+
+use crate::commands::render::render_parsed;
+use crate::minesweeper::importers;
+
+const USAGE: &str = "Usage: ms_renderer render <replay file> [--gif] [--full] [-o <output file>]";
+
+/// Renders a replay file without Discord, returns the exit code of the process.
+pub fn run(args: &[String]) -> i32 {
+    match render(args) {
+        Ok(output) => {
+            println!("Rendered the game to {output}");
+            0
+        }
+        Err(error) => {
+            eprintln!("{error}");
+            1
+        }
+    }
+}
+
+fn render(args: &[String]) -> Result<String, String> {
+    let mut args = args.iter();
+    if args.next().map(String::as_str) != Some("render") {
+        return Err(USAGE.to_string());
+    }
+
+    let mut input = None;
+    let mut output = None;
+    let mut gif = false;
+    let mut full = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--gif" => gif = true,
+            "--full" => full = true,
+            "-o" => output = Some(args.next().ok_or(USAGE)?.clone()),
+            _ if input.is_none() => input = Some(arg.clone()),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+
+    let input = input.ok_or(USAGE)?;
+    let data = fs::read(&input).map_err(|error| format!("Unable to read {input}: {error}"))?;
+    let parsed = importers::import(input.as_str(), &data).map_err(|error| error.to_string())?;
+
+    //Large boards are always rendered as image, the extension has to match
+    let gif = gif && parsed.metadata.x_size <= 32 && parsed.metadata.y_size <= 32;
+    let game_data = render_parsed(parsed, gif, full, None).map_err(|error| error.to_string())?;
+
+    let output = output.unwrap_or_else(|| "game".to_string() + if gif { ".gif" } else { ".webp" });
+    fs::write(&output, game_data.image_data)
+        .map_err(|error| format!("Unable to write {output}: {error}"))?;
+
+    Ok(output)
+}
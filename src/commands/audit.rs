@@ -0,0 +1,163 @@
+use serenity::builder::CreateApplicationCommand;
+use serenity::client::Context;
+use serenity::model::Permissions;
+use serenity::model::application::command::CommandOptionType;
+use serenity::model::application::interaction::MessageFlags;
+use serenity::model::application::interaction::application_command::ApplicationCommandInteraction;
+use serenity::utils::Color;
+
+use crate::commands::error::CommandError;
+use crate::commands::render::{
+    error_response, fetch_player_data, parse_game_data, provider_choices,
+};
+use crate::minesweeper::provider::registry::{ProviderRegistry, fetch_blocking, get_registry};
+use crate::minesweeper::validator;
+
+/// More anomalies are summarized, so the report fits into the embed description.
+const MAX_LISTED_ANOMALIES: usize = 15;
+
+pub(crate) async fn run(command: &ApplicationCommandInteraction, ctx: &Context) {
+    let option = |name: &str| {
+        command
+            .data
+            .options
+            .iter()
+            .find(|x| x.name.eq(name))
+            .and_then(|x| x.value.as_ref())
+            .and_then(|x| x.as_str())
+    };
+
+    let registry = get_registry(ctx).await;
+    let Some(provider) = registry.get_or_default(option("provider")) else {
+        error_response(command, ctx, "Unknown Provider").await;
+        return;
+    };
+
+    let game_id = option("game_id").unwrap_or_default().trim();
+    if !provider.is_valid_game_id(game_id) {
+        error_response(
+            command,
+            ctx,
+            format!("This is not a valid {} game id", provider.name()).as_str(),
+        )
+        .await;
+        return;
+    }
+
+    let fetch_id = game_id.to_string();
+    let api_data = match fetch_blocking(ctx, provider, move |provider| {
+        provider.fetch_data(fetch_id.as_str())
+    })
+    .await
+    {
+        Ok(api_data) => api_data,
+        Err(error) => {
+            let error = CommandError::from_fetch(provider.name(), error);
+            error_response(command, ctx, error.to_string().as_str()).await;
+            return;
+        }
+    };
+
+    let Some(game_data) = &api_data.game_data else {
+        error_response(command, ctx, "There is no replay data for this game").await;
+        return;
+    };
+
+    let parsed_data = match parse_game_data(game_data) {
+        Ok(parsed_data) => parsed_data,
+        Err(error) => {
+            error_response(command, ctx, error.to_string().as_str()).await;
+            return;
+        }
+    };
+
+    // Replaying runs the solver for every guess, which must not hold up the async runtime
+    let (report, api_data) = tokio::task::spawn_blocking(move || {
+        (validator::validate(&parsed_data, Some(&api_data)), api_data)
+    })
+    .await
+    .expect("Validation panicked");
+    let player_data = fetch_player_data(ctx, provider, &api_data).await;
+
+    let mut description = report
+        .anomalies
+        .iter()
+        .take(MAX_LISTED_ANOMALIES)
+        .map(|anomaly| format!("- {anomaly}"))
+        .collect::<Vec<String>>()
+        .join("\n");
+    if report.anomalies.len() > MAX_LISTED_ANOMALIES {
+        description += format!(
+            "\n… and {} more",
+            report.anomalies.len() - MAX_LISTED_ANOMALIES
+        )
+        .as_str();
+    }
+    if description.is_empty() {
+        description = "No anomalies found".to_string();
+    }
+
+    let result = if report.won {
+        "Won"
+    } else if report.lost {
+        "Lost"
+    } else {
+        "Not finished"
+    };
+
+    let color = if report.is_suspicious() {
+        Color::from_rgb(255, 50, 50)
+    } else {
+        Color::from_rgb(102, 187, 106)
+    };
+
+    let response = command
+        .create_followup_message(&ctx.http, |message| {
+            message
+                .embed(|e| {
+                    e.title(format!("Audit of {} game {game_id}", provider.name()))
+                        .description(description)
+                        .field("Username", player_data.name.as_str(), true)
+                        .field("Replay result", result, true)
+                        .field("", "", false)
+                        .field("Actions", report.actions, true)
+                        .field("Guesses", report.guesses, true)
+                        .color(color)
+                })
+                .flags(MessageFlags::EPHEMERAL)
+        })
+        .await;
+
+    if let Err(error) = response {
+        println!("Was unable to respond to command! {:?}", error)
+    }
+}
+
+pub fn register<'a>(
+    command: &'a mut CreateApplicationCommand,
+    registry: &ProviderRegistry,
+) -> &'a mut CreateApplicationCommand {
+    command
+        .name("ms-audit")
+        .description("Checks the replay of a game for signs of cheating")
+        .default_member_permissions(Permissions::MODERATE_MEMBERS)
+        .dm_permission(false)
+        .create_option(|option| {
+            option
+                .name("game_id")
+                .description("The GameID of the Minesweeper round")
+                .kind(CommandOptionType::String)
+                .required(true)
+        })
+        .create_option(|option| {
+            option
+                .name("provider")
+                .description(format!(
+                    "Where the game was played (Default: {})",
+                    registry.default_provider().name()
+                ))
+                .kind(CommandOptionType::String)
+                .required(false);
+            provider_choices(option, registry)
+        })
+}
@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use serenity::builder::CreateApplicationCommand;
+use serenity::client::Context;
+use serenity::model::Permissions;
+use serenity::model::application::command::CommandOptionType;
+use serenity::model::application::interaction::application_command::ApplicationCommandInteraction;
+use serenity::model::channel::Message;
+use serenity::model::id::UserId;
+use serenity::prelude::TypeMapKey;
+use serenity::utils::Color;
+
+use crate::commands::render::{
+    create_game_embed, error_response, game_attachment, render_game, save_to_history,
+};
+use crate::minesweeper::provider::registry::get_registry;
+use crate::storage::store::get_storage;
+
+/// Only the first few games of a message are rendered to keep the channel readable.
+const MAX_GAMES_PER_MESSAGE: usize = 3;
+const USER_COOLDOWN: Duration = Duration::from_secs(30);
+
+pub struct AutoRenderCooldowns;
+
+impl TypeMapKey for AutoRenderCooldowns {
+    type Value = HashMap<UserId, Instant>;
+}
+
+pub(crate) async fn run(command: &ApplicationCommandInteraction, ctx: &Context) {
+    let Some(guild_id) = command.guild_id else {
+        error_response(command, ctx, "This command can only be used in a server").await;
+        return;
+    };
+
+    let enabled = command
+        .data
+        .options
+        .iter()
+        .find(|x| x.name.eq("enabled"))
+        .and_then(|x| x.value.as_ref())
+        .and_then(|x| x.as_bool())
+        .unwrap_or(true);
+
+    let storage = get_storage(ctx).await;
+    let result = storage
+        .write(|data| {
+            data.auto_render_guilds.retain(|x| *x != guild_id.0);
+
+            if enabled {
+                data.auto_render_guilds.push(guild_id.0);
+            }
+        })
+        .await;
+
+    if result.is_err() {
+        error_response(command, ctx, "Unable to save the setting").await;
+        return;
+    }
+
+    let result = command
+        .create_followup_message(&ctx.http, |message| {
+            message.embed(|e| {
+                e.description(if enabled {
+                    "Game ids and links posted in this server will now be rendered"
+                } else {
+                    "Game ids and links posted in this server will no longer be rendered"
+                })
+                .color(Color::from_rgb(102, 187, 106))
+            })
+        })
+        .await;
+
+    if let Err(error) = result {
+        println!("Was unable to respond to command! {:?}", error)
+    }
+}
+
+pub(crate) async fn on_message(ctx: &Context, msg: &Message) {
+    if msg.author.bot {
+        return;
+    }
+
+    let Some(guild_id) = msg.guild_id else {
+        return;
+    };
+
+    let storage = get_storage(ctx).await;
+    let enabled = storage
+        .read(|data| data.auto_render_guilds.contains(&guild_id.0))
+        .await;
+
+    if !enabled {
+        return;
+    }
+
+    let registry = get_registry(ctx).await;
+    let mut games = registry.find_games(msg.content.as_str());
+    games.truncate(MAX_GAMES_PER_MESSAGE);
+
+    //Single words are common in chat, a bare id has to contain a digit to be rendered
+    if games.is_empty() {
+        games.extend(
+            registry
+                .bare_game(msg.content.as_str())
+                .filter(|(_, game_id)| game_id.chars().any(|c| c.is_ascii_digit())),
+        );
+    }
+
+    if games.is_empty() || is_on_cooldown(ctx, msg.author.id).await {
+        return;
+    }
+
+    for (provider, game_id) in games {
+        //Errors are not reported, the message might just have looked like a game id
+        let Ok(rendered_game) = render_game(ctx, provider, game_id.as_str(), &false, &false).await
+        else {
+            continue;
+        };
+
+        save_to_history(
+            ctx,
+            provider,
+            game_id.as_str(),
+            &rendered_game.api_data,
+            msg.author.id,
+        )
+        .await;
+
+        let result = msg
+            .channel_id
+            .send_message(&ctx.http, |message| {
+                message
+                    .reference_message(msg)
+                    .allowed_mentions(|mentions| mentions.replied_user(false))
+                    .embed(|e| {
+                        create_game_embed(
+                            e,
+                            &registry,
+                            provider,
+                            game_id.as_str(),
+                            &rendered_game.api_data,
+                            &rendered_game.player_data,
+                            Some(&rendered_game.game_data),
+                        )
+                    });
+
+                message.add_file(game_attachment(&rendered_game.game_data, false));
+
+                message
+            })
+            .await;
+
+        if let Err(error) = result {
+            println!("Was unable to reply to message! {:?}", error)
+        }
+    }
+}
+
+/// Returns whether the user triggered a render recently, otherwise starts a new cooldown.
+async fn is_on_cooldown(ctx: &Context, user_id: UserId) -> bool {
+    let mut data = ctx.data.write().await;
+    let cooldowns = data
+        .get_mut::<AutoRenderCooldowns>()
+        .expect("Cooldowns were not initialized");
+
+    let now = Instant::now();
+    cooldowns.retain(|_, last| now.duration_since(*last) < USER_COOLDOWN);
+
+    if cooldowns.contains_key(&user_id) {
+        return true;
+    }
+
+    cooldowns.insert(user_id, now);
+    false
+}
+
+pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
+    command
+        .name("ms-autorender")
+        .description("Render game ids and links posted in this server automatically")
+        .dm_permission(false)
+        .default_member_permissions(Permissions::MANAGE_GUILD)
+        .create_option(|option| {
+            option
+                .name("enabled")
+                .description("Whether games should be rendered automatically (Default: true)")
+                .kind(CommandOptionType::Boolean)
+                .required(false)
+        })
+}
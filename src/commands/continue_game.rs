@@ -0,0 +1,141 @@
+use std::time::Instant;
+
+use serenity::builder::CreateApplicationCommand;
+use serenity::client::Context;
+use serenity::model::application::command::CommandOptionType;
+use serenity::model::application::interaction::application_command::ApplicationCommandInteraction;
+
+use crate::commands::error::CommandError;
+use crate::commands::play::{ContinuedGame, PlaySession, start_session};
+use crate::commands::render::{
+    error_response, last_linked_game, parse_game_data, provider_choices,
+};
+use crate::minesweeper::game_session::GameSession;
+use crate::minesweeper::provider::registry::{ProviderRegistry, fetch_blocking, get_registry};
+
+pub(crate) async fn run(command: &ApplicationCommandInteraction, ctx: &Context) {
+    let option = |name: &str| {
+        command
+            .data
+            .options
+            .iter()
+            .find(|x| x.name.eq(name))
+            .and_then(|x| x.value.as_ref())
+            .and_then(|x| x.as_str())
+    };
+
+    let registry = get_registry(ctx).await;
+    let Some(provider) = registry.get_or_default(option("provider")) else {
+        error_response(command, ctx, "Unknown Provider").await;
+        return;
+    };
+
+    let game_id = match option("game_id") {
+        Some(game_id) => game_id.trim().to_string(),
+        None => match last_linked_game(ctx, provider, command.user.id).await {
+            Some(game_id) => game_id,
+            None => {
+                error_response(
+                    command,
+                    ctx,
+                    "Please provide a game id or link your Minecraft account with /ms-link",
+                )
+                .await;
+                return;
+            }
+        },
+    };
+
+    if !provider.is_valid_game_id(game_id.as_str()) {
+        error_response(
+            command,
+            ctx,
+            format!("This is not a valid {} game id", provider.name()).as_str(),
+        )
+        .await;
+        return;
+    }
+
+    let fetch_id = game_id.clone();
+    let api_data = match fetch_blocking(ctx, provider, move |provider| {
+        provider.fetch_data(fetch_id.as_str())
+    })
+    .await
+    {
+        Ok(api_data) => api_data,
+        Err(error) => {
+            let error = CommandError::from_fetch(provider.name(), error);
+            error_response(command, ctx, error.to_string().as_str()).await;
+            return;
+        }
+    };
+
+    let Some(game_data) = &api_data.game_data else {
+        error_response(command, ctx, "There is no replay data for this game").await;
+        return;
+    };
+
+    let parsed_data = match parse_game_data(game_data) {
+        Ok(parsed_data) => parsed_data,
+        Err(error) => {
+            error_response(command, ctx, error.to_string().as_str()).await;
+            return;
+        }
+    };
+
+    let game = match GameSession::before_loss(
+        parsed_data.game_board,
+        &parsed_data.open_data,
+        &parsed_data.flag_data,
+        &parsed_data.chord_data,
+    ) {
+        Ok(game) => game,
+        Err(error) => {
+            error_response(command, ctx, error.to_string().as_str()).await;
+            return;
+        }
+    };
+
+    let session = PlaySession {
+        owner: command.user.id,
+        title: format!("What if: {} game {game_id}", provider.name()),
+        seed: game_id.clone(),
+        game,
+        daily: None,
+        continued_from: Some(ContinuedGame {
+            name: format!("{}:{game_id}", provider.id()),
+            game_data: game_data.clone(),
+        }),
+        last_move: Instant::now(),
+    };
+
+    start_session(command, ctx, session).await;
+}
+
+pub fn register<'a>(
+    command: &'a mut CreateApplicationCommand,
+    registry: &ProviderRegistry,
+) -> &'a mut CreateApplicationCommand {
+    command
+        .name("ms-continue")
+        .description("Continue a lost game from right before the mine was hit")
+        .create_option(|option| {
+            option
+                .name("game_id")
+                .description("The GameID of the lost Minesweeper round (Default: your last game)")
+                .kind(CommandOptionType::String)
+                .set_autocomplete(true)
+                .required(false)
+        })
+        .create_option(|option| {
+            option
+                .name("provider")
+                .description(format!(
+                    "Where the game was played (Default: {})",
+                    registry.default_provider().name()
+                ))
+                .kind(CommandOptionType::String)
+                .required(false);
+            provider_choices(option, registry)
+        })
+}
@@ -0,0 +1,373 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::time::Instant;
+
+use chrono::{NaiveDate, Utc};
+use serenity::builder::CreateApplicationCommand;
+use serenity::client::Context;
+use serenity::model::application::command::CommandOptionType;
+use serenity::model::application::interaction::application_command::ApplicationCommandInteraction;
+use serenity::model::channel::AttachmentType::Bytes;
+use serenity::utils::Color;
+
+use crate::commands::play::{PlaySession, start_session};
+use crate::commands::render::error_response;
+use crate::minesweeper::duration::format_duration;
+use crate::minesweeper::error::MinesweeperError;
+use crate::minesweeper::game_session::{GameSession, GameState};
+use crate::minesweeper::generator::generator::{BoardSettings, Difficulty, GeneratorType};
+use crate::minesweeper::generator::random::seed_from_str;
+use crate::minesweeper::minesweeper_logic::FieldState;
+use crate::minesweeper::parsers;
+use crate::minesweeper::renderer::Renderer;
+use crate::storage::daily::{DailyStart, DailySubmission, find_submission, leaderboard};
+use crate::storage::error::StorageError;
+use crate::storage::store::get_storage;
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const MAX_LEADERBOARD_ENTRIES: usize = 20;
+
+/// The guild and day a daily puzzle was started for.
+#[derive(Clone, Debug)]
+pub struct DailyPuzzle {
+    pub guild_id: u64,
+    /// Day of the puzzle as `YYYY-MM-DD` in UTC
+    pub date: String,
+}
+
+/// Everyone in a guild plays the same board on a day, the guild is part of the seed so the
+/// board can not be practised in another guild first.
+/// The board is purely random, which means the first click can be a mine for everyone.
+pub fn daily_settings(guild_id: u64, date: &str, secret: &str) -> BoardSettings {
+    let (x_size, y_size, mine_count) = Difficulty::Intermediate.size();
+
+    BoardSettings {
+        x_size,
+        y_size,
+        mine_count,
+        generator_type: GeneratorType::Random,
+        seed: seed_from_str(format!("daily:{secret}:{guild_id}:{date}").as_str()),
+    }
+}
+
+/// Without a secret in the seed, `/ms-play` with the same seed could be used to practise the
+/// puzzle. It is taken from `DAILY_SECRET` or generated once and kept in the storage.
+async fn daily_secret(ctx: &Context) -> Result<String, StorageError> {
+    if let Ok(secret) = std::env::var("DAILY_SECRET") {
+        return Ok(secret);
+    }
+
+    let storage = get_storage(ctx).await;
+    if let Some(secret) = storage.read(|data| data.daily_secret.clone()).await {
+        return Ok(secret);
+    }
+
+    storage
+        .write(|data| {
+            data.daily_secret
+                .get_or_insert_with(|| {
+                    let random = || RandomState::new().hash_one(Utc::now().timestamp_nanos_opt());
+                    format!("{:016x}{:016x}", random(), random())
+                })
+                .clone()
+        })
+        .await
+}
+
+pub(crate) async fn run(command: &ApplicationCommandInteraction, ctx: &Context) {
+    let Some(subcommand) = command.data.options.first() else {
+        error_response(command, ctx, "Unknown subcommand").await;
+        return;
+    };
+
+    let Some(guild_id) = command.guild_id else {
+        error_response(
+            command,
+            ctx,
+            "The daily puzzle can only be played in a server",
+        )
+        .await;
+        return;
+    };
+
+    match subcommand.name.as_str() {
+        "play" => play(command, ctx, guild_id.0).await,
+        "leaderboard" => {
+            let date = subcommand
+                .options
+                .iter()
+                .find(|x| x.name.eq("date"))
+                .and_then(|x| x.value.as_ref())
+                .and_then(|x| x.as_str())
+                .map(|x| x.trim().to_string());
+            show_leaderboard(command, ctx, guild_id.0, date).await
+        }
+        _ => error_response(command, ctx, "Unknown subcommand").await,
+    }
+}
+
+async fn play(command: &ApplicationCommandInteraction, ctx: &Context, guild_id: u64) {
+    let date = Utc::now().format(DATE_FORMAT).to_string();
+    let user_id = command.user.id;
+
+    let Ok(secret) = daily_secret(ctx).await else {
+        error_response(command, ctx, "Unable to save the daily puzzle").await;
+        return;
+    };
+
+    let game = match GameSession::new(daily_settings(guild_id, &date, &secret)) {
+        Ok(game) => game,
+        Err(error) => {
+            error_response(command, ctx, error.to_string().as_str()).await;
+            return;
+        }
+    };
+
+    //The start is stored before the board is shown, so abandoning the game or a restart of the
+    //bot does not allow another attempt
+    let storage = get_storage(ctx).await;
+    let started = storage
+        .write(|data| {
+            let started =
+                find_submission(&data.daily_submissions, guild_id, &date, user_id.0).is_some()
+                    || data.daily_starts.iter().any(|x| {
+                        x.guild_id == guild_id && x.date == date && x.user_id == user_id.0
+                    });
+
+            data.daily_starts.retain(|x| x.date == date);
+            if !started {
+                data.daily_starts.push(DailyStart {
+                    guild_id,
+                    date: date.clone(),
+                    user_id: user_id.0,
+                });
+            }
+
+            started
+        })
+        .await;
+
+    match started {
+        Ok(false) => {}
+        Ok(true) => {
+            error_response(command, ctx, "You already played today's puzzle").await;
+            return;
+        }
+        Err(_) => {
+            error_response(command, ctx, "Unable to save the daily puzzle").await;
+            return;
+        }
+    }
+
+    let session = PlaySession {
+        owner: user_id,
+        title: format!("Daily puzzle {date}"),
+        seed: String::new(),
+        game,
+        daily: Some(DailyPuzzle { guild_id, date }),
+        continued_from: None,
+        last_move: Instant::now(),
+    };
+
+    start_session(command, ctx, session).await;
+}
+
+/// Stores the finished game, only the first submission of a user per day counts.
+pub(crate) async fn submit(ctx: &Context, puzzle: &DailyPuzzle, session: &PlaySession) {
+    let submission = DailySubmission {
+        guild_id: puzzle.guild_id,
+        date: puzzle.date.clone(),
+        user_id: session.owner.0,
+        replay: session.game.to_replay(),
+        won: session.game.state == GameState::Won,
+        time: session.game.duration(),
+    };
+
+    let storage = get_storage(ctx).await;
+    let result = storage
+        .write(|data| {
+            let submitted = find_submission(
+                &data.daily_submissions,
+                submission.guild_id,
+                &submission.date,
+                submission.user_id,
+            )
+            .is_some();
+
+            if !submitted {
+                data.daily_submissions.push(submission);
+            }
+        })
+        .await;
+
+    if let Err(error) = result {
+        println!("Was unable to save the daily submission! {:?}", error)
+    }
+}
+
+async fn show_leaderboard(
+    command: &ApplicationCommandInteraction,
+    ctx: &Context,
+    guild_id: u64,
+    date: Option<String>,
+) {
+    let today = Utc::now().date_naive();
+    let date = match date {
+        Some(date) => match NaiveDate::parse_from_str(date.as_str(), DATE_FORMAT) {
+            Ok(date) if date <= today => date,
+            Ok(_) => {
+                error_response(command, ctx, "This puzzle is not available yet").await;
+                return;
+            }
+            Err(_) => {
+                error_response(command, ctx, "The date has to look like 2024-01-31").await;
+                return;
+            }
+        },
+        None => today,
+    };
+    let date_string = date.format(DATE_FORMAT).to_string();
+
+    let storage = get_storage(ctx).await;
+    let ranking = storage
+        .read(|data| leaderboard(&data.daily_submissions, guild_id, &date_string))
+        .await;
+
+    if ranking.is_empty() {
+        error_response(
+            command,
+            ctx,
+            format!("Nobody played the daily puzzle of {date_string} yet").as_str(),
+        )
+        .await;
+        return;
+    }
+
+    let description = ranking
+        .iter()
+        .take(MAX_LEADERBOARD_ENTRIES)
+        .enumerate()
+        .map(|(index, submission)| {
+            let result = if submission.won {
+                format_duration(submission.time.max(0) as u64)
+            } else {
+                "Lost".to_string()
+            };
+            format!("`{}.` <@{}> - {result}", index + 1, submission.user_id)
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    // Today's mines stay hidden for everyone who did not play yet
+    let image_data = render_first_clicks(&ranking, date < today);
+
+    let result = command
+        .create_followup_message(&ctx.http, |message| {
+            message.embed(|e| {
+                e.title(format!("Daily puzzle {date_string}"))
+                    .description(description)
+                    .footer(|f| f.text("Red fields show where the players clicked first"))
+                    .color(Color::from_rgb(255, 202, 40));
+
+                if image_data.is_ok() {
+                    e.image("attachment://daily.webp");
+                }
+
+                e
+            });
+
+            if let Ok(image_data) = image_data {
+                message.add_file(Bytes {
+                    data: Cow::from(image_data),
+                    filename: "daily.webp".to_string(),
+                });
+            }
+
+            message
+        })
+        .await;
+
+    if let Err(error) = result {
+        println!("Was unable to respond to command! {:?}", error)
+    }
+}
+
+/// Renders the board of the day with the first clicks of all submissions tinted red.
+/// The board is read from a submitted replay, so the secret of the seed is not needed.
+fn render_first_clicks(
+    submissions: &[DailySubmission],
+    reveal: bool,
+) -> Result<Vec<u8>, MinesweeperError> {
+    let submission = submissions
+        .first()
+        .ok_or(MinesweeperError::GameDataNotFound)?;
+    let mut board = parsers::parse_game(submission.replay.as_str())?.game_board;
+
+    if reveal {
+        board
+            .fields
+            .iter_mut()
+            .flatten()
+            .for_each(|field| field.field_state = FieldState::Open);
+    }
+
+    let mut first_clicks: HashMap<(i32, i32), u32> = HashMap::new();
+    for submission in submissions {
+        if let Some(click) = first_click(submission.replay.as_str()) {
+            *first_clicks.entry(click).or_insert(0) += 1;
+        }
+    }
+
+    let fields: Vec<(i32, i32, u32)> = first_clicks
+        .into_iter()
+        .map(|((x, y), count)| (x, y, count))
+        .collect();
+
+    Renderer::new(
+        board.metadata.clone(),
+        board,
+        Vec::new(),
+        Vec::new(),
+        Vec::new(),
+        &false,
+        &true,
+    )
+    .render_heatmap(&fields)
+}
+
+/// The first opened field of a replay.
+fn first_click(replay: &str) -> Option<(i32, i32)> {
+    parsers::parse_game(replay)
+        .ok()?
+        .open_data
+        .first()
+        .map(|action| (action.x, action.y))
+}
+
+pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
+    command
+        .name("ms-daily")
+        .description("Play the same board as everyone else today")
+        .dm_permission(false)
+        .create_option(|option| {
+            option
+                .name("play")
+                .description("Play today's puzzle, every player has one attempt")
+                .kind(CommandOptionType::SubCommand)
+        })
+        .create_option(|option| {
+            option
+                .name("leaderboard")
+                .description("Show the ranking and first clicks of a daily puzzle")
+                .kind(CommandOptionType::SubCommand)
+                .create_sub_option(|option| {
+                    option
+                        .name("date")
+                        .description("Day of the puzzle, e.g. 2024-01-31 (Default: today)")
+                        .kind(CommandOptionType::String)
+                        .required(false)
+                })
+        })
+}
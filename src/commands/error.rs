@@ -1,11 +1,44 @@
 use thiserror::Error;
 
+use crate::minesweeper::error::MinesweeperError;
+
 #[derive(Error, Debug)]
 pub enum CommandError {
+    #[error("Unable to fetch game data")]
+    FetchGameData,
+    #[error("This game could not be found.")]
+    GameNotFound,
+    #[error("The bot is not allowed to use the {0} API.")]
+    Unauthorized(String),
+    #[error("Too many requests were sent to the {0} API, please try again later.")]
+    RateLimited(String),
+    #[error("{0} API is down, please try again later.")]
+    UpstreamDown(String),
     #[error("The Game Data is from an unsupported version.")]
     UnsupportedVersion,
     #[error("Image could not be rendered.")]
     ImageRender,
     #[error("The data seems to be corrupted.")]
     DataParse,
+    #[error("Only .avf, .rmv and .txt replay files are supported.")]
+    UnsupportedReplayFile,
+    #[error("The replay file seems to be corrupted.")]
+    InvalidReplayFile,
+    #[error("Boards larger than 100x100 can not be rendered.")]
+    BoardTooLarge,
+}
+
+impl CommandError {
+    /// Tells the user why a provider could not deliver a game, naming the provider if it is at fault.
+    pub fn from_fetch(provider_name: &str, error: MinesweeperError) -> CommandError {
+        match error {
+            MinesweeperError::GameDataNotFound => CommandError::GameNotFound,
+            MinesweeperError::Unauthorized | MinesweeperError::ApiKeyNotFound => {
+                CommandError::Unauthorized(provider_name.to_string())
+            }
+            MinesweeperError::RateLimited => CommandError::RateLimited(provider_name.to_string()),
+            MinesweeperError::UpstreamDown => CommandError::UpstreamDown(provider_name.to_string()),
+            _ => CommandError::FetchGameData,
+        }
+    }
 }
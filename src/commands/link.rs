@@ -0,0 +1,279 @@
+use std::collections::HashMap;
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+
+use chrono::Utc;
+use serenity::builder::CreateApplicationCommand;
+use serenity::client::Context;
+use serenity::model::application::command::CommandOptionType;
+use serenity::model::application::interaction::MessageFlags;
+use serenity::model::application::interaction::application_command::ApplicationCommandInteraction;
+use serenity::utils::Color;
+
+use crate::commands::render::error_response;
+use crate::minesweeper::error::MinesweeperError;
+use crate::minesweeper::provider::mojang;
+use crate::storage::links::{AccountLink, PendingLink, normalize_uuid};
+use crate::storage::store::get_storage;
+
+/// Seconds the player has to enter the code in-game.
+const CODE_VALIDITY: i64 = 10 * 60;
+/// Without 0/O and 1/I, which are easily mixed up when typing the code in-game.
+const CODE_CHARACTERS: &str = "ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+const CODE_LENGTH: usize = 6;
+
+pub(crate) async fn run(command: &ApplicationCommandInteraction, ctx: &Context) {
+    let Some(subcommand) = command.data.options.first() else {
+        error_response(command, ctx, "Unknown subcommand").await;
+        return;
+    };
+
+    match subcommand.name.as_str() {
+        "start" => {
+            let name = subcommand
+                .options
+                .iter()
+                .find(|x| x.name.eq("name"))
+                .and_then(|x| x.value.as_ref())
+                .and_then(|x| x.as_str())
+                .unwrap_or("")
+                .trim();
+            start(command, ctx, name).await
+        }
+        "verify" => verify(command, ctx).await,
+        "remove" => remove(command, ctx).await,
+        _ => error_response(command, ctx, "Unknown subcommand").await,
+    }
+}
+
+async fn start(command: &ApplicationCommandInteraction, ctx: &Context, name: &str) {
+    if link_codes_path().is_none() {
+        error_response(command, ctx, "Linking accounts is not set up for this bot").await;
+        return;
+    }
+
+    if !mojang::is_valid_name(name) {
+        error_response(command, ctx, "This is not a valid Minecraft name").await;
+        return;
+    }
+
+    let profile = {
+        let name = name.to_string();
+        tokio::task::spawn_blocking(move || mojang::fetch_profile(name.as_str())).await
+    };
+    let Ok(Ok(profile)) = profile else {
+        error_response(command, ctx, "There is no Minecraft account with this name").await;
+        return;
+    };
+
+    let discord_user = command.user.id.0;
+    let code = generate_code();
+
+    let storage = get_storage(ctx).await;
+    let result = storage
+        .write(|data| {
+            data.pending_links
+                .retain(|x| x.discord_user != discord_user);
+            data.pending_links.push(PendingLink {
+                discord_user,
+                uuid: profile.id.clone(),
+                name: profile.name.clone(),
+                code: code.clone(),
+                created_at: Utc::now().timestamp(),
+            });
+        })
+        .await;
+
+    if result.is_err() {
+        error_response(command, ctx, "Unable to save the link").await;
+        return;
+    }
+
+    success_response(
+        command,
+        ctx,
+        format!(
+            "Join the server as **{}** and enter `/link {}` within {} minutes, then run `/ms-link verify`",
+            profile.name,
+            code,
+            CODE_VALIDITY / 60
+        )
+        .as_str(),
+    )
+    .await;
+}
+
+async fn verify(command: &ApplicationCommandInteraction, ctx: &Context) {
+    let discord_user = command.user.id.0;
+    let now = Utc::now().timestamp();
+
+    let storage = get_storage(ctx).await;
+    let pending_link = storage
+        .read(|data| {
+            data.pending_links
+                .iter()
+                .find(|x| x.discord_user == discord_user && now - x.created_at <= CODE_VALIDITY)
+                .cloned()
+        })
+        .await;
+
+    let Some(pending_link) = pending_link else {
+        error_response(
+            command,
+            ctx,
+            "There is no pending link, start one with `/ms-link start`",
+        )
+        .await;
+        return;
+    };
+
+    let uuid = pending_link.uuid.clone();
+    let link_code = tokio::task::spawn_blocking(move || fetch_link_code(uuid.as_str()))
+        .await
+        .unwrap_or(Err(MinesweeperError::UpstreamDown));
+    match link_code {
+        Ok(Some(code)) if code.eq_ignore_ascii_case(pending_link.code.as_str()) => {}
+        Ok(_) => {
+            error_response(
+                command,
+                ctx,
+                format!(
+                    "The code `{}` was not entered in-game yet",
+                    pending_link.code
+                )
+                .as_str(),
+            )
+            .await;
+            return;
+        }
+        Err(_) => {
+            error_response(command, ctx, "Unable to check the code, try again later").await;
+            return;
+        }
+    }
+
+    let uuid = normalize_uuid(pending_link.uuid.as_str());
+    let result = storage
+        .write(|data| {
+            data.pending_links
+                .retain(|x| x.discord_user != discord_user);
+            //A Minecraft account can only be linked to one Discord user and the other way around
+            data.links.retain(|x| {
+                x.discord_user != discord_user && normalize_uuid(x.uuid.as_str()) != uuid
+            });
+            data.links.push(AccountLink {
+                discord_user,
+                uuid: pending_link.uuid.clone(),
+                name: pending_link.name.clone(),
+            });
+        })
+        .await;
+
+    if result.is_err() {
+        error_response(command, ctx, "Unable to save the link").await;
+        return;
+    }
+
+    success_response(
+        command,
+        ctx,
+        format!("Your account is now linked to **{}**", pending_link.name).as_str(),
+    )
+    .await;
+}
+
+async fn remove(command: &ApplicationCommandInteraction, ctx: &Context) {
+    let discord_user = command.user.id.0;
+
+    let storage = get_storage(ctx).await;
+    let result = storage
+        .write(|data| {
+            data.pending_links
+                .retain(|x| x.discord_user != discord_user);
+            data.links.retain(|x| x.discord_user != discord_user);
+        })
+        .await;
+
+    if result.is_err() {
+        error_response(command, ctx, "Unable to remove the link").await;
+        return;
+    }
+
+    success_response(command, ctx, "Your Minecraft account is no longer linked").await;
+}
+
+/// Codes are checked against the file in `LINK_CODES_PATH`, a JSON object mapping UUIDs to the
+/// entered codes which the Minecraft server writes. None of the APIs offers the codes.
+fn fetch_link_code(uuid: &str) -> Result<Option<String>, MinesweeperError> {
+    let path = link_codes_path().ok_or(MinesweeperError::Unsupported)?;
+    let raw = std::fs::read_to_string(path).map_err(|_| MinesweeperError::GameDataNotFound)?;
+    let codes: HashMap<String, String> =
+        serde_json::from_str(raw.as_str()).map_err(|_| MinesweeperError::ApiDataParse)?;
+
+    Ok(codes
+        .into_iter()
+        .find(|(key, _)| normalize_uuid(key) == normalize_uuid(uuid))
+        .map(|(_, code)| code))
+}
+
+fn link_codes_path() -> Option<String> {
+    std::env::var("LINK_CODES_PATH").ok()
+}
+
+fn generate_code() -> String {
+    let characters: Vec<char> = CODE_CHARACTERS.chars().collect();
+    let mut random = RandomState::new().hash_one(Utc::now().timestamp_nanos_opt());
+
+    (0..CODE_LENGTH)
+        .map(|_| {
+            let character = characters[(random % characters.len() as u64) as usize];
+            random /= characters.len() as u64;
+            character
+        })
+        .collect()
+}
+
+async fn success_response(command: &ApplicationCommandInteraction, ctx: &Context, text: &str) {
+    let result = command
+        .create_followup_message(&ctx.http, |message| {
+            message
+                .embed(|e| e.description(text).color(Color::from_rgb(102, 187, 106)))
+                .flags(MessageFlags::EPHEMERAL)
+        })
+        .await;
+
+    if let Err(error) = result {
+        println!("Was unable to respond to command! {:?}", error)
+    }
+}
+
+pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
+    command
+        .name("ms-link")
+        .description("Link your Discord account to your Minecraft account")
+        .create_option(|option| {
+            option
+                .name("start")
+                .description("Start linking a Minecraft account")
+                .kind(CommandOptionType::SubCommand)
+                .create_sub_option(|option| {
+                    option
+                        .name("name")
+                        .description("Your Minecraft name")
+                        .kind(CommandOptionType::String)
+                        .required(true)
+                })
+        })
+        .create_option(|option| {
+            option
+                .name("verify")
+                .description("Finish linking after entering the code in-game")
+                .kind(CommandOptionType::SubCommand)
+        })
+        .create_option(|option| {
+            option
+                .name("remove")
+                .description("Unlink your Minecraft account")
+                .kind(CommandOptionType::SubCommand)
+        })
+}
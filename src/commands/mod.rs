@@ -1,2 +1,10 @@
-mod error;
+pub mod audit;
+pub mod auto_render;
+pub mod continue_game;
+pub mod daily;
+pub(crate) mod error;
+pub mod link;
+pub mod play;
 pub mod render;
+pub mod render_message;
+pub mod subscribe;
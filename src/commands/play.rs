@@ -0,0 +1,695 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use serenity::builder::{CreateApplicationCommand, CreateComponents, CreateEmbed};
+use serenity::client::Context;
+use serenity::model::application::command::CommandOptionType;
+use serenity::model::application::component::{ActionRowComponent, ButtonStyle, InputTextStyle};
+use serenity::model::application::interaction::application_command::ApplicationCommandInteraction;
+use serenity::model::application::interaction::message_component::MessageComponentInteraction;
+use serenity::model::application::interaction::modal::ModalSubmitInteraction;
+use serenity::model::application::interaction::{InteractionResponseType, MessageFlags};
+use serenity::model::channel::AttachmentType::Bytes;
+use serenity::model::channel::Message;
+use serenity::model::id::UserId;
+use serenity::prelude::TypeMapKey;
+use serenity::utils::Color;
+
+use crate::commands::daily::{self, DailyPuzzle};
+use crate::commands::render::error_response;
+use crate::minesweeper::base36;
+use crate::minesweeper::duration::format_duration;
+use crate::minesweeper::game_session::{GameSession, GameState};
+use crate::minesweeper::generator::generator::{
+    BoardSettings, Difficulty, GeneratorType, generate,
+};
+use crate::minesweeper::generator::random::seed_from_str;
+use crate::minesweeper::minesweeper_logic::MAX_BOARD_SIZE;
+use crate::minesweeper::parsers;
+use crate::minesweeper::renderer::{Renderer, side_by_side};
+
+#[derive(Clone)]
+pub struct PlaySession {
+    pub owner: UserId,
+    pub title: String,
+    /// Seed as entered by the user, so the board can be shared
+    pub seed: String,
+    pub game: GameSession,
+    /// Set if this is a daily puzzle, finished games are submitted to its leaderboard
+    pub daily: Option<DailyPuzzle>,
+    /// Game this session continues after its loss
+    pub continued_from: Option<ContinuedGame>,
+    /// Abandoned games are dropped after [`SESSION_TIMEOUT`] without a move
+    pub last_move: Instant,
+}
+
+/// A lost game which a session continues, shown next to the alternate ending once it is over.
+#[derive(Clone)]
+pub struct ContinuedGame {
+    /// As `<provider>:<game id>`
+    pub name: String,
+    /// Replay of the original game
+    pub game_data: String,
+}
+
+/// Games without a move for this long are given up, so abandoned games do not pile up.
+const SESSION_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+/// Running games by the id of the message showing them.
+pub struct PlaySessions;
+
+impl TypeMapKey for PlaySessions {
+    type Value = HashMap<u64, PlaySession>;
+}
+
+pub(crate) async fn run(command: &ApplicationCommandInteraction, ctx: &Context) {
+    let option = |name: &str| {
+        command
+            .data
+            .options
+            .iter()
+            .find(|x| x.name.eq(name))
+            .and_then(|x| x.value.as_ref())
+    };
+
+    let difficulty = option("difficulty")
+        .and_then(|x| x.as_str())
+        .and_then(|x| {
+            Difficulty::ALL
+                .into_iter()
+                .find(|difficulty| difficulty.name().eq_ignore_ascii_case(x))
+        })
+        .unwrap_or(Difficulty::Beginner);
+
+    let generator_type = option("generator")
+        .and_then(|x| x.as_str())
+        .and_then(|x| GeneratorType::from_str(x).ok())
+        .unwrap_or(GeneratorType::FirstClickOpening);
+
+    let seed = option("seed")
+        .and_then(|x| x.as_str())
+        .map(|x| x.trim().to_string())
+        .unwrap_or_else(|| {
+            base36::encode(Utc::now().timestamp_nanos_opt().unwrap_or(0).abs() % 36_i64.pow(8))
+        });
+
+    let (x_size, y_size, mine_count) = difficulty.size();
+    let width = option("width").and_then(|x| x.as_i64());
+    let height = option("height").and_then(|x| x.as_i64());
+    let mines = option("mines").and_then(|x| x.as_i64());
+    let custom = width.is_some() || height.is_some() || mines.is_some();
+
+    //Discord enforces the limits of the options, anything out of range is rejected instead of truncated
+    let (Ok(x_size), Ok(y_size), Ok(mine_count)) = (
+        width.map_or(Ok(x_size), i32::try_from),
+        height.map_or(Ok(y_size), i32::try_from),
+        mines.map_or(Ok(mine_count), u32::try_from),
+    ) else {
+        error_response(command, ctx, "The board size or mine count is invalid").await;
+        return;
+    };
+
+    let settings = BoardSettings {
+        x_size,
+        y_size,
+        mine_count,
+        generator_type,
+        seed: seed_from_str(seed.as_str()),
+    };
+
+    let game = match GameSession::new(settings) {
+        Ok(game) => game,
+        Err(error) => {
+            error_response(command, ctx, error.to_string().as_str()).await;
+            return;
+        }
+    };
+
+    let session = PlaySession {
+        owner: command.user.id,
+        title: if custom {
+            format!("Minesweeper {}x{}", settings.x_size, settings.y_size)
+        } else {
+            format!("Minesweeper {}", difficulty.name())
+        },
+        seed,
+        game,
+        daily: None,
+        continued_from: None,
+        last_move: Instant::now(),
+    };
+
+    start_session(command, ctx, session).await;
+}
+
+/// Posts the board of a new session and starts listening for moves on it.
+pub(crate) async fn start_session(
+    command: &ApplicationCommandInteraction,
+    ctx: &Context,
+    session: PlaySession,
+) {
+    let Ok(image_data) = render_board(&session.game) else {
+        error_response(command, ctx, "Image could not be rendered.").await;
+        return;
+    };
+
+    let result = command
+        .create_followup_message(&ctx.http, |message| {
+            message
+                .embed(|e| create_board_embed(e, &session))
+                .add_file(Bytes {
+                    data: Cow::from(image_data),
+                    filename: "board.webp".to_string(),
+                })
+                .set_components(create_buttons())
+        })
+        .await;
+
+    match result {
+        Ok(message) => {
+            let mut data = ctx.data.write().await;
+            let sessions = data
+                .get_mut::<PlaySessions>()
+                .expect("Play sessions were not initialized");
+
+            sessions.retain(|_, session| session.last_move.elapsed() < SESSION_TIMEOUT);
+            sessions.insert(message.id.0, session);
+        }
+        Err(error) => println!("Was unable to respond to command! {:?}", error),
+    }
+}
+
+pub(crate) async fn on_component(component: &MessageComponentInteraction, ctx: &Context) {
+    let owner = {
+        let data = ctx.data.read().await;
+        data.get::<PlaySessions>()
+            .expect("Play sessions were not initialized")
+            .get(&component.message.id.0)
+            .map(|session| session.owner)
+    };
+
+    let Some(owner) = owner else {
+        ephemeral_response(component, ctx, "This game is already over").await;
+        return;
+    };
+
+    if owner != component.user.id {
+        ephemeral_response(
+            component,
+            ctx,
+            "Only the player can make moves in this game",
+        )
+        .await;
+        return;
+    }
+
+    let result = match component.data.custom_id.as_str() {
+        "ms-play:give-up" => {
+            let result = component
+                .create_interaction_response(&ctx.http, |response| {
+                    response.kind(InteractionResponseType::DeferredUpdateMessage)
+                })
+                .await;
+
+            let session = {
+                let mut data = ctx.data.write().await;
+                data.get_mut::<PlaySessions>()
+                    .expect("Play sessions were not initialized")
+                    .remove(&component.message.id.0)
+            };
+
+            if let Some(session) = session {
+                update_message(ctx, &mut component.message.clone(), &session).await;
+                on_finished(ctx, &session).await;
+            }
+
+            result
+        }
+        custom_id => {
+            let title = match custom_id {
+                "ms-play:flag" => "Place or remove a flag",
+                "ms-play:chord" => "Open the fields around a number",
+                _ => "Open a field",
+            };
+
+            component
+                .create_interaction_response(&ctx.http, |response| {
+                    response
+                        .kind(InteractionResponseType::Modal)
+                        .interaction_response_data(|data| {
+                            data.custom_id(custom_id).title(title).components(|c| {
+                                c.create_action_row(|row| {
+                                    row.create_input_text(|input| {
+                                        input
+                                            .custom_id("field")
+                                            .label("Field (column letter and row, e.g. C4)")
+                                            .style(InputTextStyle::Short)
+                                            .min_length(2)
+                                            .max_length(5)
+                                            .required(true)
+                                    })
+                                })
+                            })
+                        })
+                })
+                .await
+        }
+    };
+
+    if let Err(error) = result {
+        println!("Was unable to respond to interaction! {:?}", error)
+    }
+}
+
+pub(crate) async fn on_modal_submit(modal: &ModalSubmitInteraction, ctx: &Context) {
+    let Some(message) = &modal.message else {
+        return;
+    };
+
+    let input = modal
+        .data
+        .components
+        .iter()
+        .flat_map(|row| row.components.iter())
+        .find_map(|component| match component {
+            ActionRowComponent::InputText(input) if input.custom_id == "field" => {
+                Some(input.value.clone())
+            }
+            _ => None,
+        })
+        .unwrap_or_default();
+
+    //Opening the first field generates the board, which takes a while for no-guess boards.
+    //It happens without holding the lock, so other games and commands are not blocked.
+    let pending_board = {
+        let data = ctx.data.read().await;
+        data.get::<PlaySessions>()
+            .expect("Play sessions were not initialized")
+            .get(&message.id.0)
+            .filter(|session| session.owner == modal.user.id && session.game.needs_board())
+            .filter(|_| modal.data.custom_id == "ms-play:open")
+            .and_then(|session| {
+                let metadata = session.game.metadata();
+                parse_field(input.as_str(), metadata.x_size, metadata.y_size)
+                    .map(|field| (session.game.settings, field))
+            })
+    };
+
+    let deferred = pending_board.is_some();
+    if let Some((settings, field)) = pending_board {
+        let result = modal
+            .create_interaction_response(&ctx.http, |response| {
+                response.kind(InteractionResponseType::DeferredUpdateMessage)
+            })
+            .await;
+        if let Err(error) = result {
+            println!("Was unable to respond to interaction! {:?}", error)
+        }
+
+        let board = tokio::task::spawn_blocking(move || generate(&settings, Some(field)))
+            .await
+            .expect("Board generation panicked");
+
+        match board {
+            Ok(board) => {
+                let mut data = ctx.data.write().await;
+                if let Some(session) = data
+                    .get_mut::<PlaySessions>()
+                    .expect("Play sessions were not initialized")
+                    .get_mut(&message.id.0)
+                    .filter(|session| session.game.needs_board())
+                {
+                    session.game.place_board(board);
+                }
+            }
+            Err(error) => {
+                error_followup(modal, ctx, error.to_string().as_str()).await;
+                return;
+            }
+        }
+    }
+
+    let now = Utc::now().timestamp_millis();
+    let session = {
+        let mut data = ctx.data.write().await;
+        let sessions = data
+            .get_mut::<PlaySessions>()
+            .expect("Play sessions were not initialized");
+
+        let Some(session) = sessions.get_mut(&message.id.0) else {
+            return;
+        };
+
+        if session.owner != modal.user.id {
+            return;
+        }
+
+        session.last_move = Instant::now();
+        let metadata = session.game.metadata();
+        let result = match parse_field(input.as_str(), metadata.x_size, metadata.y_size) {
+            Some((x, y)) if modal.data.custom_id == "ms-play:flag" => {
+                session.game.toggle_flag(x, y, now);
+                Ok(())
+            }
+            Some((x, y)) if modal.data.custom_id == "ms-play:chord" => {
+                session.game.chord(x, y, now);
+                Ok(())
+            }
+            Some((x, y)) => session
+                .game
+                .open(x, y, now)
+                .map_err(|error| error.to_string()),
+            None => Err(format!("`{input}` is not a field on this board")),
+        };
+
+        match result {
+            Ok(_) if session.game.state == GameState::Running => Ok(session.clone()),
+            Ok(_) => Ok(sessions
+                .remove(&message.id.0)
+                .expect("Session was removed while in use")),
+            Err(error) => Err(error),
+        }
+    };
+
+    let result = match &session {
+        Ok(_) if deferred => Ok(()),
+        Ok(_) => {
+            modal
+                .create_interaction_response(&ctx.http, |response| {
+                    response.kind(InteractionResponseType::DeferredUpdateMessage)
+                })
+                .await
+        }
+        Err(error) if deferred => {
+            error_followup(modal, ctx, error).await;
+            Ok(())
+        }
+        Err(error) => {
+            modal
+                .create_interaction_response(&ctx.http, |response| {
+                    response
+                        .kind(InteractionResponseType::ChannelMessageWithSource)
+                        .interaction_response_data(|data| {
+                            data.content(error).flags(MessageFlags::EPHEMERAL)
+                        })
+                })
+                .await
+        }
+    };
+
+    if let Err(error) = result {
+        println!("Was unable to respond to interaction! {:?}", error)
+    }
+
+    if let Ok(session) = session {
+        update_message(ctx, &mut message.clone(), &session).await;
+
+        if session.game.state != GameState::Running {
+            on_finished(ctx, &session).await;
+        }
+    }
+}
+
+/// Errors after the interaction was deferred are sent as a new message only the player sees.
+async fn error_followup(modal: &ModalSubmitInteraction, ctx: &Context, error: &str) {
+    let result = modal
+        .create_followup_message(&ctx.http, |message| {
+            message.content(error).flags(MessageFlags::EPHEMERAL)
+        })
+        .await;
+
+    if let Err(error) = result {
+        println!("Was unable to respond to interaction! {:?}", error)
+    }
+}
+
+async fn on_finished(ctx: &Context, session: &PlaySession) {
+    if let Some(puzzle) = &session.daily {
+        daily::submit(ctx, puzzle, session).await;
+    }
+}
+
+/// Replaces the board image and removes the buttons once the game is over.
+async fn update_message(ctx: &Context, message: &mut Message, session: &PlaySession) {
+    let finished = session.game.state != GameState::Running;
+    let image_data = match &session.continued_from {
+        Some(original) if finished => render_comparison(original, &session.game),
+        _ => render_board(&session.game),
+    };
+    let Ok(image_data) = image_data else {
+        return;
+    };
+
+    let replay = session.game.to_replay();
+
+    let result = message
+        .edit(&ctx.http, |m| {
+            m.remove_all_attachments()
+                .attachment(Bytes {
+                    data: Cow::from(image_data),
+                    filename: "board.webp".to_string(),
+                })
+                .embed(|e| create_board_embed(e, session));
+
+            if finished {
+                m.attachment(Bytes {
+                    data: Cow::from(replay.into_bytes()),
+                    filename: "replay.txt".to_string(),
+                })
+                .set_components(CreateComponents::default())
+            } else {
+                m.set_components(create_buttons())
+            }
+        })
+        .await;
+
+    if let Err(error) = result {
+        println!("Was unable to update the game! {:?}", error)
+    }
+}
+
+fn render_board(game: &GameSession) -> Result<Vec<u8>, ()> {
+    Renderer::new(
+        game.metadata().clone(),
+        game.board.clone(),
+        Vec::new(),
+        Vec::new(),
+        Vec::new(),
+        &false,
+        &true,
+    )
+    .render_current_state()
+    .map_err(|_| ())
+}
+
+/// The end of the original game on the left, the alternate ending on the right.
+fn render_comparison(original: &ContinuedGame, game: &GameSession) -> Result<Vec<u8>, ()> {
+    let original = parsers::parse_game(original.game_data.as_str()).map_err(|_| ())?;
+    let original = Renderer::new(
+        original.metadata,
+        original.game_board,
+        original.open_data,
+        original.flag_data,
+        original.chord_data,
+        &false,
+        &true,
+    )
+    .render_jpeg()
+    .map_err(|_| ())?;
+
+    side_by_side(&original, &render_board(game)?).map_err(|_| ())
+}
+
+fn create_board_embed<'a>(e: &'a mut CreateEmbed, session: &PlaySession) -> &'a mut CreateEmbed {
+    let game = &session.game;
+    let now = Utc::now().timestamp_millis();
+
+    let (status, color) = match game.state {
+        GameState::Running => ("Running", Color::from_rgb(66, 165, 245)),
+        GameState::Won => ("Won", Color::from_rgb(102, 187, 106)),
+        GameState::Lost => ("Lost", Color::from_rgb(255, 138, 101)),
+    };
+
+    e.title(&session.title)
+        .field("Player", format!("<@{}>", session.owner), true)
+        .field(
+            "Time",
+            format_duration(game.elapsed(now).max(0) as u64),
+            true,
+        )
+        .field(
+            "Flags",
+            format!("{}/{}", game.flag_count(), game.mine_count()),
+            true,
+        )
+        .field("Status", status, true);
+
+    match (&session.daily, &session.continued_from) {
+        (Some(puzzle), _) => e.field("Daily puzzle", &puzzle.date, true),
+        (None, Some(original)) => e.field("Continues", format!("`{}`", original.name), true),
+        (None, None) => e
+            .field("Generator", game.settings.generator_type.name(), true)
+            .field("Seed", format!("`{}`", session.seed), true),
+    };
+
+    let footer = match session.continued_from {
+        Some(_) if game.state != GameState::Running => {
+            "The original game on the left, the alternate ending on the right"
+        }
+        _ => "Columns are letters from the left, rows are numbers from the top",
+    };
+
+    e.image("attachment://board.webp")
+        .footer(|f| f.text(footer))
+        .color(color)
+}
+
+fn create_buttons() -> CreateComponents {
+    let mut components = CreateComponents::default();
+    components.create_action_row(|row| {
+        row.create_button(|button| {
+            button
+                .custom_id("ms-play:open")
+                .label("Open")
+                .style(ButtonStyle::Primary)
+        })
+        .create_button(|button| {
+            button
+                .custom_id("ms-play:flag")
+                .label("Flag")
+                .style(ButtonStyle::Secondary)
+        })
+        .create_button(|button| {
+            button
+                .custom_id("ms-play:chord")
+                .label("Chord")
+                .style(ButtonStyle::Secondary)
+        })
+        .create_button(|button| {
+            button
+                .custom_id("ms-play:give-up")
+                .label("Give up")
+                .style(ButtonStyle::Danger)
+        })
+    });
+    components
+}
+
+async fn ephemeral_response(component: &MessageComponentInteraction, ctx: &Context, text: &str) {
+    let result = component
+        .create_interaction_response(&ctx.http, |response| {
+            response
+                .kind(InteractionResponseType::ChannelMessageWithSource)
+                .interaction_response_data(|data| data.content(text).flags(MessageFlags::EPHEMERAL))
+        })
+        .await;
+
+    if let Err(error) = result {
+        println!("Was unable to respond to interaction! {:?}", error)
+    }
+}
+
+/// Parses a field like `C4` into zero based `(x, y)` coordinates.
+/// Columns continue with `AA` after `Z`, rows start at 1.
+fn parse_field(input: &str, x_size: i32, y_size: i32) -> Option<(i32, i32)> {
+    let input: String = input
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != ',')
+        .collect::<String>()
+        .to_ascii_uppercase();
+
+    let split = input.find(|c: char| c.is_ascii_digit())?;
+    let (column, row) = input.split_at(split);
+
+    if column.is_empty() || !column.chars().all(|c| c.is_ascii_uppercase()) {
+        return None;
+    }
+
+    let x = column
+        .chars()
+        .fold(0, |result, c| result * 26 + (c as i32 - 'A' as i32 + 1))
+        - 1;
+    let y = row.parse::<i32>().ok()? - 1;
+
+    if x < 0 || x >= x_size || y < 0 || y >= y_size {
+        return None;
+    }
+
+    Some((x, y))
+}
+
+pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
+    command
+        .name("ms-play")
+        .description("Play a round of Minesweeper")
+        .create_option(|option| {
+            let option = option
+                .name("difficulty")
+                .description("Size and mine count of the board (Default: Beginner)")
+                .kind(CommandOptionType::String)
+                .required(false);
+
+            for difficulty in Difficulty::ALL {
+                let (x_size, y_size, mine_count) = difficulty.size();
+                option.add_string_choice(
+                    format!(
+                        "{} ({x_size}x{y_size}, {mine_count} mines)",
+                        difficulty.name()
+                    ),
+                    difficulty.name().to_lowercase(),
+                );
+            }
+
+            option
+        })
+        .create_option(|option| {
+            let option = option
+                .name("generator")
+                .description("How the mines are placed (Default: Opening first click)")
+                .kind(CommandOptionType::String)
+                .required(false);
+
+            for generator_type in GeneratorType::ALL {
+                option.add_string_choice(generator_type.name(), generator_type.id());
+            }
+
+            option
+        })
+        .create_option(|option| {
+            option
+                .name("seed")
+                .description("Play the same board as someone else by using their seed")
+                .kind(CommandOptionType::String)
+                .required(false)
+        })
+        .create_option(|option| {
+            option
+                .name("width")
+                .description("Custom width of the board")
+                .kind(CommandOptionType::Integer)
+                .min_int_value(2)
+                .max_int_value(MAX_BOARD_SIZE)
+                .required(false)
+        })
+        .create_option(|option| {
+            option
+                .name("height")
+                .description("Custom height of the board")
+                .kind(CommandOptionType::Integer)
+                .min_int_value(2)
+                .max_int_value(MAX_BOARD_SIZE)
+                .required(false)
+        })
+        .create_option(|option| {
+            option
+                .name("mines")
+                .description("Custom mine count")
+                .kind(CommandOptionType::Integer)
+                .min_int_value(1)
+                .max_int_value(MAX_BOARD_SIZE * MAX_BOARD_SIZE - 1)
+                .required(false)
+        })
+}
@@ -1,45 +1,45 @@
 use std::borrow::Cow;
 
-use chrono::{NaiveDateTime, Timelike};
-use serenity::builder::CreateApplicationCommand;
+use image::RgbaImage;
+use serenity::builder::{CreateApplicationCommand, CreateApplicationCommandOption, CreateEmbed};
 use serenity::client::Context;
 use serenity::model::application::command::CommandOptionType;
-use serenity::model::application::interaction::application_command::ApplicationCommandInteraction;
 use serenity::model::application::interaction::MessageFlags;
+use serenity::model::application::interaction::application_command::{
+    ApplicationCommandInteraction, CommandDataOptionValue,
+};
+use serenity::model::application::interaction::autocomplete::AutocompleteInteraction;
 use serenity::model::channel::AttachmentType::Bytes;
+use serenity::model::channel::{Attachment, AttachmentType};
+use serenity::model::id::UserId;
 use serenity::utils::Color;
 
 use crate::commands::error::CommandError;
+use crate::minesweeper::duration::format_duration;
+use crate::minesweeper::error::MinesweeperError;
+use crate::minesweeper::exporters::rawvf;
 use crate::minesweeper::game_data::GameData;
+use crate::minesweeper::importers;
 use crate::minesweeper::parsers;
-use crate::minesweeper::parsers::parser::{Iparser, ParsedData};
-use crate::minesweeper::provider::greev::greev_provider::GreevProvider;
-use crate::minesweeper::provider::mcplayhd::mcplay_provider::McPlayHdProvider;
+use crate::minesweeper::parsers::parser::ParsedData;
 use crate::minesweeper::provider::provider::{ApiData, PlayerData, Provider};
-use crate::minesweeper::renderer::Renderer;
+use crate::minesweeper::provider::registry::{ProviderRegistry, fetch_blocking, get_registry};
+use crate::minesweeper::provider::skins;
+use crate::minesweeper::renderer::{Hud, Renderer};
+use crate::minesweeper::summary_card;
+use crate::storage::history::{HistoryEntry, upsert};
+use crate::storage::links::{find_by_discord_user, find_by_uuid, normalize_uuid};
+use crate::storage::store::get_storage;
 
-const DEFAULT_PROVIDER: &str = "greev";
-const DEFAULT_PROVIDER_GREEV: &str = "greev";
-const DEFAULT_PROVIDER_MCPLAY: &str = "mcplayhd";
+/// Discord does not allow more choices in an autocomplete response.
+const MAX_AUTOCOMPLETE_CHOICES: usize = 25;
 
 pub(crate) async fn run(command: &ApplicationCommandInteraction, ctx: &Context) {
     let game_id = command.data.options.iter().find(|x| x.name.eq("game_id"));
     let use_gif = command.data.options.iter().find(|x| x.name.eq("gif"));
     let full_render = command.data.options.iter().find(|x| x.name.eq("full"));
     let option_provider = command.data.options.iter().find(|x| x.name.eq("provider"));
-
-    if game_id.is_none() || game_id.unwrap().value.as_ref().is_none() {
-        error_response(command, ctx, "Please provide a game id").await;
-        return;
-    }
-
-    let game_id = game_id
-        .expect("GameID is empty")
-        .value
-        .as_ref()
-        .expect("Unable to get value")
-        .as_str()
-        .expect("Unable to get the GameID as str");
+    let option_rawvf = command.data.options.iter().find(|x| x.name.eq("rawvf"));
 
     let gif = use_gif
         .map(|x| x.value.as_ref().unwrap().as_bool().unwrap_or(false))
@@ -49,15 +49,29 @@ pub(crate) async fn run(command: &ApplicationCommandInteraction, ctx: &Context)
         .map(|x| x.value.as_ref().unwrap().as_bool().unwrap_or(false))
         .unwrap_or(false);
 
-    let possible_providers: Vec<&dyn Provider> = vec![&GreevProvider, &McPlayHdProvider];
-
-    let provider = option_provider
-        .map(|x| x.value.as_ref().unwrap().as_str().unwrap().to_lowercase())
-        .unwrap_or(DEFAULT_PROVIDER.to_string());
+    let rawvf = option_rawvf
+        .map(|x| x.value.as_ref().unwrap().as_bool().unwrap_or(false))
+        .unwrap_or(false);
 
-    let optional_provider = possible_providers
+    let attachment = command
+        .data
+        .options
         .iter()
-        .find(|x| x.id() == provider.as_str());
+        .find(|x| x.name.eq("file"))
+        .and_then(|x| match &x.resolved {
+            Some(CommandDataOptionValue::Attachment(attachment)) => Some(attachment),
+            _ => None,
+        });
+
+    if let Some(attachment) = attachment {
+        run_file(command, ctx, attachment, gif, full).await;
+        return;
+    }
+
+    let registry = get_registry(ctx).await;
+
+    let optional_provider =
+        registry.get_or_default(option_provider.and_then(|x| x.value.as_ref()?.as_str()));
 
     if optional_provider.is_none() {
         error_response(command, ctx, "Unknown Provider").await;
@@ -66,281 +80,557 @@ pub(crate) async fn run(command: &ApplicationCommandInteraction, ctx: &Context)
 
     let provider = optional_provider.unwrap();
 
-    let result_api_data = provider.fetch_data(game_id);
-
-    if result_api_data.is_err() {
-        error_response(command, ctx, "Unable to fetch game data").await;
+    let game_id = match game_id.and_then(|x| x.value.as_ref()) {
+        Some(value) => value
+            .as_str()
+            .expect("Unable to get the GameID as str")
+            .to_string(),
+        None => match last_linked_game(ctx, provider, command.user.id).await {
+            Some(game_id) => game_id,
+            None => {
+                error_response(
+                    command,
+                    ctx,
+                    "Please provide a game id or link your Minecraft account with /ms-link",
+                )
+                .await;
+                return;
+            }
+        },
+    };
+    let game_id = game_id.as_str();
+
+    if !provider.is_valid_game_id(game_id) {
+        error_response(
+            command,
+            ctx,
+            format!("This is not a valid {} game id", provider.name()).as_str(),
+        )
+        .await;
         return;
     }
 
-    let api_data = result_api_data.unwrap();
+    let rendered_game = match render_game(ctx, provider, game_id, &gif, &full).await {
+        Ok(rendered_game) => rendered_game,
+        Err(error) => {
+            error_response(command, ctx, error.to_string().as_str()).await;
+            return;
+        }
+    };
 
-    let image_data_result = get_image_data(&api_data, &gif, &full).await;
+    save_to_history(
+        ctx,
+        provider,
+        game_id,
+        &rendered_game.api_data,
+        command.user.id,
+    )
+    .await;
 
-    if let Err(error) = image_data_result {
-        error_response(command, ctx, error.to_string().as_str()).await;
-        return;
+    let replay = rawvf
+        .then(|| rawvf_attachment(&rendered_game.api_data, &rendered_game.player_data, game_id))
+        .flatten();
+
+    let result = command
+        .create_followup_message(&ctx.http, |message| {
+            message.embed(|e| {
+                create_game_embed(
+                    e,
+                    &registry,
+                    provider,
+                    game_id,
+                    &rendered_game.api_data,
+                    &rendered_game.player_data,
+                    Some(&rendered_game.game_data),
+                )
+            });
+
+            message.add_file(game_attachment(&rendered_game.game_data, gif));
+
+            if let Some(attachment) = replay {
+                message.add_file(attachment);
+            }
+
+            message
+        })
+        .await;
+
+    if let Err(error) = result {
+        println!("Was unable to respond to command! {:?}", error)
     }
+}
 
-    let image_data_result = image_data_result.unwrap();
-    //We need to clone the image_data_result to be able to use it in the embed as it is moved
-    let image_data_result_embed = image_data_result.clone();
+pub(crate) struct RenderedGame {
+    pub api_data: ApiData,
+    pub player_data: PlayerData,
+    pub game_data: GameData,
+}
 
-    let timestamp = NaiveDateTime::from_timestamp_millis(api_data.time as i64)
-        .expect("Unable to get Timestamp from time");
+/// Fetches a game from the provider and renders it, ready to be put into an embed.
+pub(crate) async fn render_game(
+    ctx: &Context,
+    provider: &dyn Provider,
+    game_id: &str,
+    gif: &bool,
+    full: &bool,
+) -> Result<RenderedGame, CommandError> {
+    let game_id = game_id.to_string();
+    let api_data = fetch_blocking(ctx, provider, move |provider| {
+        provider.fetch_data(game_id.as_str())
+    })
+    .await
+    .map_err(|error| CommandError::from_fetch(provider.name(), error))?;
+
+    let player_data = fetch_player_data(ctx, provider, &api_data).await;
+
+    let game_data = get_image_data(&api_data, Some(&player_data), gif, full).await?;
+
+    Ok(RenderedGame {
+        api_data,
+        player_data,
+        game_data,
+    })
+}
 
-    let player_data = provider
-        .fetch_name(api_data.uuid.as_str())
-        .unwrap_or_else(|_| PlayerData {
-            name: "%".to_string(),
-        });
+/// The player sent with the game or their name from the provider, together with the Discord user
+/// who linked the account.
+pub(crate) async fn fetch_player_data(
+    ctx: &Context,
+    provider: &dyn Provider,
+    api_data: &ApiData,
+) -> PlayerData {
+    let uuid = api_data.uuid.as_str();
+    let mut player_data = match &api_data.player {
+        Some(player) => player.clone(),
+        None => {
+            let uuid = uuid.to_string();
+            fetch_blocking(ctx, provider, move |provider| {
+                provider.fetch_name(uuid.as_str())
+            })
+            .await
+            .unwrap_or_else(|_| PlayerData {
+                name: "%".to_string(),
+                group: None,
+                discord_user: None,
+                avatar_url: None,
+            })
+        }
+    };
+
+    //Discord loads the face itself, so it is only linked and not downloaded here
+    player_data.avatar_url = skins::is_reachable(uuid).then(|| skins::head_url(uuid));
+
+    let storage = get_storage(ctx).await;
+    player_data.discord_user = storage
+        .read(|data| find_by_uuid(&data.links, uuid).map(|link| link.discord_user))
+        .await;
+
+    player_data
+}
 
-    let result = match provider.id() {
-        DEFAULT_PROVIDER_GREEV => {
-            command
-                .create_followup_message(&ctx.http, |message| {
-                    let msg = message.embed(|e| {
-                        e.title(format!("{}Minesweeper Game {}", if DEFAULT_PROVIDER.eq(DEFAULT_PROVIDER_GREEV) { "" } else { "[Greev] " }, game_id))
-                            .field("Username", player_data.name, true)
-                            .field(
-                                "Time",
-                                format!(
-                                    "{:02}:{:02}:{:02}.{:03}",
-                                    timestamp.hour(),
-                                    timestamp.minute(),
-                                    timestamp.second(),
-                                    timestamp.nanosecond() / 1_000_000
-                                ),
-                                true,
-                            )
-                            .field("", "", false)
-                            .field(
-                                "Difficulty",
-                                api_data.tiepe.expect("type is required for greev"),
-                                true,
-                            )
-                            .field(
-                                "Generator",
-                                api_data.generator.expect("generator is required for greev"),
-                                true,
-                            )
-                            .field("", "", false)
-                            .field(
-                                "Correct Flags",
-                                api_data
-                                    .correct_flags
-                                    .expect("correct_flags is required for greev"),
-                                true,
-                            )
-                            .field(
-                                "Incorrect Flags",
-                                api_data
-                                    .incorrect_flags
-                                    .expect("incorrect_flags is required for greev"),
-                                true,
-                            )
-                            .field("Won", if api_data.won { "Yes" } else { "No" }, false)
-                            .color(if api_data.won {
-                                Color::from_rgb(102, 187, 106)
-                            } else {
-                                Color::from_rgb(255, 138, 101)
-                            })
-                    });
-
-                    if let Some(data) = image_data_result {
-                        return msg.add_file(Bytes {
-                            data: Cow::from(data.image_data),
-                            filename: "game".to_string() + if gif { ".gif" } else { ".webp" },
-                        });
-                    }
-
-                    msg
+/// The most recent game of the Minecraft account the user linked.
+pub(crate) async fn last_linked_game(
+    ctx: &Context,
+    provider: &dyn Provider,
+    user_id: UserId,
+) -> Option<String> {
+    let storage = get_storage(ctx).await;
+    storage
+        .read(|data| {
+            let link = find_by_discord_user(&data.links, user_id.0)?;
+
+            data.history
+                .iter()
+                .rev()
+                .find(|x| {
+                    x.provider == provider.id()
+                        && normalize_uuid(&x.uuid) == normalize_uuid(&link.uuid)
                 })
-                .await
-        }
-        DEFAULT_PROVIDER_MCPLAY => {
-            command
-                .create_followup_message(&ctx.http, |message| {
-                    let game_data = image_data_result_embed
-                        .expect("GameData is required for mcplayhd");
-                    let msg = message.embed(|e| {
-                        e.title(format!("{}Minesweeper Game {}", if DEFAULT_PROVIDER.eq(DEFAULT_PROVIDER_MCPLAY) { "" } else { "[McPlayHD] " } , game_id))
-                            .field("Username", player_data.name, true)
-                            .field(
-                                "Time",
-                                format!(
-                                    "{:02}:{:02}:{:02}.{:03}",
-                                    timestamp.hour(),
-                                    timestamp.minute(),
-                                    timestamp.second(),
-                                    timestamp.nanosecond() / 1_000_000
-                                ),
-                                true,
-                            )
-                            .field("", "", false)
-                            .field(
-                                "Correct Flags",
-                                api_data
-                                    .correct_flags
-                                    .expect("correct_flags is required for mcplayhd"),
-                                true,
-                            )
-                            .field(
-                                "Incorrect Flags",
-                                api_data
-                                    .incorrect_flags
-                                    .expect("incorrect_flags is required for mcplayhd"),
-                                true,
-                            )
-                            .field(
-                                "Uncovered Fields",
-                                game_data.opened_fields.to_string() + "/" + (game_data.total_fields - game_data.mine_count).to_string().as_str(),
-                                true,
-                            )
-                            .field("Won", if api_data.won { "Yes" } else { "No" }, false)
-                            .color(if api_data.won {
-                                Color::from_rgb(102, 187, 106)
-                            } else {
-                                Color::from_rgb(255, 138, 101)
-                            })
-                    });
-
-                    if let Some(data) = image_data_result {
-                        return msg.add_file(Bytes {
-                            data: Cow::from(data.image_data),
-                            filename: "game".to_string() + if gif { ".gif" } else { ".webp" },
-                        });
-                    }
-
-                    msg
+                .map(|x| x.game_id.clone())
+        })
+        .await
+}
+
+/// Remembers that the user rendered the game, used for suggestions and record detection.
+pub(crate) async fn save_to_history(
+    ctx: &Context,
+    provider: &dyn Provider,
+    game_id: &str,
+    api_data: &ApiData,
+    user_id: UserId,
+) {
+    let mut entry = HistoryEntry::new(provider.id(), game_id, api_data);
+    entry.rendered_by.push(user_id.0);
+
+    let storage = get_storage(ctx).await;
+    if let Err(error) = storage.write(|data| upsert(&mut data.history, entry)).await {
+        println!("Unable to save game {game_id}: {error}");
+    }
+}
+
+/// Suggests the games the user rendered most recently for the `game_id` option.
+pub(crate) async fn autocomplete(autocomplete: &AutocompleteInteraction, ctx: &Context) {
+    let typed = autocomplete
+        .data
+        .options
+        .iter()
+        .find(|x| x.focused && x.name.eq("game_id"))
+        .and_then(|x| x.value.as_ref())
+        .and_then(|x| x.as_str())
+        .unwrap_or("")
+        .trim()
+        .to_string();
+
+    let provider_id = autocomplete
+        .data
+        .options
+        .iter()
+        .find(|x| x.name.eq("provider"))
+        .and_then(|x| x.value.as_ref())
+        .and_then(|x| x.as_str());
+
+    let registry = get_registry(ctx).await;
+    let Some(provider) = registry.get_or_default(provider_id) else {
+        return;
+    };
+
+    let user_id = autocomplete.user.id.0;
+    let storage = get_storage(ctx).await;
+    let suggestions: Vec<HistoryEntry> = storage
+        .read(|data| {
+            let linked_uuid =
+                find_by_discord_user(&data.links, user_id).map(|link| normalize_uuid(&link.uuid));
+
+            data.history
+                .iter()
+                .rev()
+                .filter(|x| x.provider == provider.id())
+                .filter(|x| {
+                    x.rendered_by.contains(&user_id)
+                        || linked_uuid
+                            .as_ref()
+                            .is_some_and(|uuid| normalize_uuid(&x.uuid) == *uuid)
                 })
-                .await
+                .filter(|x| x.game_id.to_lowercase().starts_with(&typed.to_lowercase()))
+                .take(MAX_AUTOCOMPLETE_CHOICES)
+                .cloned()
+                .collect()
+        })
+        .await;
+
+    let result = autocomplete
+        .create_autocomplete_response(&ctx.http, |response| {
+            if !typed.is_empty() && !provider.is_valid_game_id(typed.as_str()) {
+                return response.add_string_choice(
+                    format!("{typed} is not a valid {} game id", provider.name()),
+                    typed.as_str(),
+                );
+            }
+
+            for entry in suggestions {
+                response.add_string_choice(
+                    format!(
+                        "{} - {}, {}, {}",
+                        entry.game_id,
+                        entry.category,
+                        format_duration(entry.time),
+                        if entry.won { "Won" } else { "Lost" }
+                    ),
+                    entry.game_id,
+                );
+            }
+
+            response
+        })
+        .await;
+
+    if let Err(error) = result {
+        println!("Was unable to respond to autocomplete! {:?}", error)
+    }
+}
+
+/// Adds a choice for every configured provider, the default one is used if none is chosen.
+pub(crate) fn provider_choices<'a>(
+    option: &'a mut CreateApplicationCommandOption,
+    registry: &ProviderRegistry,
+) -> &'a mut CreateApplicationCommandOption {
+    for provider in registry.providers() {
+        option.add_string_choice(provider.name(), provider.id());
+    }
+    option
+}
+
+/// Builds the embed describing a game, shared by `/ms` and the subscription poster.
+pub(crate) fn create_game_embed<'a>(
+    e: &'a mut CreateEmbed,
+    registry: &ProviderRegistry,
+    provider: &dyn Provider,
+    game_id: &str,
+    api_data: &ApiData,
+    player_data: &PlayerData,
+    game_data: Option<&GameData>,
+) -> &'a mut CreateEmbed {
+    let display = provider.display(api_data, game_data);
+
+    let color = match display.color {
+        Some((r, g, b)) => Color::from_rgb(r, g, b),
+        None if api_data.won => Color::from_rgb(102, 187, 106),
+        None => Color::from_rgb(255, 138, 101),
+    };
+
+    //Games of other providers than the default one are marked in the title
+    let title = if registry.is_default(provider) {
+        format!("Minesweeper Game {game_id}")
+    } else {
+        let prefix = display
+            .title_prefix
+            .unwrap_or(format!("[{}] ", provider.name()));
+        format!("{prefix}Minesweeper Game {game_id}")
+    };
+
+    e.title(title)
+        .field("Username", username(player_data), true);
+
+    if let Some(avatar_url) = &player_data.avatar_url {
+        e.thumbnail(avatar_url);
+    }
+
+    if let Some(group) = &player_data.group {
+        e.field("Group", group, true);
+    }
+
+    e.field("Time", format_duration(api_data.time), true);
+
+    if let Some(played_at) = api_data.started_at.or(api_data.ended_at) {
+        e.field("Played", discord_timestamp(played_at), true);
+    }
+
+    //Line breaks of the provider are skipped if they would leave an empty row
+    let mut row_empty = true;
+    for field in display.fields {
+        if field.is_line_break() {
+            row_empty = true;
+            continue;
         }
-        _ => {
-            command
-                .create_followup_message(&ctx.http, |message| {
-                    let msg = message.embed(|e| {
-                        e.title(format!("Minesweeper Game {}", game_id))
-                            .field("Username", player_data.name, true)
-                            .field(
-                                "Time",
-                                format!(
-                                    "{:02}:{:02}:{:02}.{:03}",
-                                    timestamp.hour(),
-                                    timestamp.minute(),
-                                    timestamp.second(),
-                                    timestamp.nanosecond() / 1_000_000
-                                ),
-                                true,
-                            )
-                            .field("Won", if api_data.won { "Yes" } else { "No" }, false)
-                            .color(if api_data.won {
-                                Color::from_rgb(102, 187, 106)
-                            } else {
-                                Color::from_rgb(255, 138, 101)
-                            })
-                    });
-
-                    if let Some(data) = image_data_result {
-                        return msg.add_file(Bytes {
-                            data: Cow::from(data.image_data),
-                            filename: "game".to_string() + if gif { ".gif" } else { ".webp" },
-                        });
-                    }
-
-                    msg
-                })
-                .await
+        if row_empty {
+            e.field("", "", false);
+            row_empty = false;
         }
-    };
+        e.field(field.label, field.value, field.inline);
+    }
 
-    if let Err(error) = result {
-        println!("Was unable to respond to command! {:?}", error)
+    e.field("Won", if api_data.won { "Yes" } else { "No" }, false)
+        .color(color)
+}
+
+/// Discord shows the timestamp in the timezone of each reader.
+fn discord_timestamp(millis: u64) -> String {
+    format!("<t:{}:f>", millis / 1000)
+}
+
+fn username(player_data: &PlayerData) -> String {
+    match player_data.discord_user {
+        Some(discord_user) => format!("{} (<@{}>)", player_data.name, discord_user),
+        None => player_data.name.clone(),
+    }
+}
+
+pub(crate) fn game_attachment(game_data: &GameData, gif: bool) -> AttachmentType<'static> {
+    //Summary cards are never animated
+    let gif = gif && !game_data.summary;
+    Bytes {
+        data: Cow::from(game_data.image_data.clone()),
+        filename: "game".to_string() + if gif { ".gif" } else { ".webp" },
     }
 }
 
-async fn get_image_data(
+/// Exports the game as `.rawvf` for desktop replay viewers, games without game data have no replay.
+pub(crate) fn rawvf_attachment(
+    api_data: &ApiData,
+    player_data: &PlayerData,
+    game_id: &str,
+) -> Option<AttachmentType<'static>> {
+    let game_data = parse_game_data(api_data.game_data.as_ref()?).ok()?;
+
+    Some(Bytes {
+        data: Cow::from(rawvf::write(&game_data, player_data.name.as_str()).into_bytes()),
+        filename: format!("{game_id}.rawvf"),
+    })
+}
+
+/// Parses the game data of the API into the board and the actions of the game.
+pub(crate) fn parse_game_data(game_data: &str) -> Result<ParsedData, CommandError> {
+    parsers::parse_game(game_data).map_err(|error| match error {
+        MinesweeperError::UnsupportedVersion => CommandError::UnsupportedVersion,
+        MinesweeperError::InvalidBoardSize => CommandError::BoardTooLarge,
+        _ => CommandError::DataParse,
+    })
+}
+
+/// Renders the replay of the game, or a summary card if the provider sent no replay data.
+/// The player is drawn above the replay if the HUD is enabled.
+pub(crate) async fn get_image_data(
     api_data: &ApiData,
-    mut gif: &bool,
+    player_data: Option<&PlayerData>,
+    gif: &bool,
     full: &bool,
-) -> Result<Option<GameData>, CommandError> {
-    if let Some(game_data) = &api_data.game_data {
-        let option = game_data.split_once('=').expect("Unable to get Version");
+) -> Result<GameData, CommandError> {
+    let Some(game_data) = &api_data.game_data else {
+        let head = head_or_placeholder(api_data.uuid.as_str()).await;
+        return render_summary(api_data, &head);
+    };
 
-        let possible_parsers: Vec<&dyn Iparser> = vec![
-            &parsers::v1::parser::ParserV1,
-            &parsers::v2::parser::ParserV2,
-        ];
+    let hud = match player_data.filter(|_| skins::hud_enabled()) {
+        Some(player_data) => Some(Hud {
+            head: head_or_placeholder(api_data.uuid.as_str()).await,
+            name: player_data.name.clone(),
+        }),
+        None => None,
+    };
 
-        let option_found_parser = possible_parsers
-            .iter()
-            .find(|p| p.supported_versions().contains(&option.0));
+    let game_data = parse_game_data(game_data)?;
+    render_parsed(game_data, *gif, *full, hud)
+}
 
-        if option_found_parser.is_none() {
-            return Err(CommandError::UnsupportedVersion);
-        }
+/// The face of the player for rendered images, the placeholder is drawn if the skin service
+/// is unreachable. Downloading blocks, so it runs on the blocking thread pool.
+async fn head_or_placeholder(uuid: &str) -> RgbaImage {
+    let uuid = uuid.to_string();
+    tokio::task::spawn_blocking(move || skins::head_or_placeholder(uuid.as_str()))
+        .await
+        .unwrap_or_else(|_| skins::placeholder_head())
+}
 
-        let parser = option_found_parser.unwrap();
+fn render_summary(api_data: &ApiData, head: &RgbaImage) -> Result<GameData, CommandError> {
+    let image_data = summary_card::render(api_data, head).map_err(|_| CommandError::ImageRender)?;
+    let (width, height) = api_data.size.unwrap_or_default();
+
+    Ok(GameData {
+        image_data,
+        opened_fields: 0,
+        total_fields: width.saturating_mul(height),
+        mine_count: api_data.mines.unwrap_or_default(),
+        summary: true,
+    })
+}
 
-        let split: Vec<&str> = option.1.split('+').collect();
+/// Renders a parsed game, shared by the providers and imported replay files.
+pub(crate) fn render_parsed(
+    game_data: ParsedData,
+    mut gif: bool,
+    full: bool,
+    hud: Option<Hud>,
+) -> Result<GameData, CommandError> {
+    //If the field is too large overwrite the gif value to not render a gif
+    if game_data.metadata.x_size > 32 || game_data.metadata.y_size > 32 {
+        gif = false
+    }
 
-        let metadata = parser.parse_meta_data(split[0].trim());
+    let mut renderer = Renderer::new(
+        game_data.metadata,
+        game_data.game_board,
+        game_data.open_data,
+        game_data.flag_data,
+        game_data.chord_data,
+        &gif,
+        &full,
+    );
+    if let Some(hud) = hud {
+        renderer = renderer.with_hud(hud);
+    }
 
-        let game_data = ParsedData {
-            game_board: parser.parse_mine_data(split[1].trim(), &metadata),
-            open_data: parser.parse_open_data(split[2].trim()),
-            flag_data: parser.parse_flag_data(split[3].trim()),
-            metadata,
-        };
+    let image_data = if gif {
+        renderer
+            .render_gif()
+            .map_err(|_| CommandError::ImageRender)?
+    } else {
+        renderer
+            .render_jpeg()
+            .map_err(|_| CommandError::ImageRender)?
+    };
+
+    Ok(GameData {
+        image_data,
+        opened_fields: renderer.game_board.open_fields,
+        total_fields: renderer.game_board.total_fields,
+        mine_count: renderer.game_board.mine_count,
+        summary: false,
+    })
+}
 
-        //If the field is too large overwrite the gif value to not render a gif
-        if game_data.metadata.x_size > 32 || game_data.metadata.y_size > 32 {
-            gif = &false
+/// Renders a replay file attached to `/ms` instead of a game of a provider.
+async fn run_file(
+    command: &ApplicationCommandInteraction,
+    ctx: &Context,
+    attachment: &Attachment,
+    gif: bool,
+    full: bool,
+) {
+    let data = match attachment.download().await {
+        Ok(data) => data,
+        Err(_) => {
+            error_response(command, ctx, "Unable to download the replay file").await;
+            return;
         }
+    };
 
-        let mut renderer = Renderer::new(
-            game_data.metadata,
-            game_data.game_board,
-            game_data.open_data,
-            game_data.flag_data,
-            gif,
-            full,
-        );
-
-        let image_data = if *gif {
-            renderer
-                .render_gif()
-                .map_err(|_| CommandError::ImageRender)?
-        } else {
-            renderer
-                .render_jpeg()
-                .map_err(|_| CommandError::ImageRender)?
-        };
-
-        Ok(Some(GameData {
-            image_data,
-            total_actions: (renderer.open_data.len() + renderer.flag_data.len()) as u8,
-            opened_fields: renderer.game_board.open_fields as u8,
-            closed_fields: (renderer.game_board.total_fields - renderer.game_board.open_fields)
-                as u8,
-            total_fields: renderer.game_board.total_fields as u8,
-            mine_count: renderer.game_board.mine_count as u8,
-        }))
-    } else {
-        Ok(None)
+    let rendered = importers::import(attachment.filename.as_str(), &data)
+        .map_err(|error| match error {
+            MinesweeperError::UnsupportedReplayFile => CommandError::UnsupportedReplayFile,
+            MinesweeperError::UnsupportedVersion => CommandError::UnsupportedVersion,
+            MinesweeperError::InvalidBoardSize => CommandError::BoardTooLarge,
+            _ => CommandError::InvalidReplayFile,
+        })
+        .and_then(|parsed| {
+            let size = (parsed.metadata.x_size, parsed.metadata.y_size);
+            render_parsed(parsed, gif, full, None).map(|game_data| (size, game_data))
+        });
+
+    let ((x_size, y_size), game_data) = match rendered {
+        Ok(rendered) => rendered,
+        Err(error) => {
+            error_response(command, ctx, error.to_string().as_str()).await;
+            return;
+        }
+    };
+
+    let result = command
+        .create_followup_message(&ctx.http, |message| {
+            message
+                .embed(|e| {
+                    e.title(attachment.filename.as_str())
+                        .field("Size", format!("{x_size}x{y_size}"), true)
+                        .field("Mines", game_data.mine_count, true)
+                        .field(
+                            "Opened fields",
+                            format!("{}/{}", game_data.opened_fields, game_data.total_fields),
+                            true,
+                        )
+                        .color(Color::from_rgb(0, 255, 0))
+                })
+                .add_file(game_attachment(&game_data, gif))
+        })
+        .await;
+
+    if let Err(error) = result {
+        println!("Was unable to respond to command! {:?}", error)
     }
 }
 
-pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
+pub fn register<'a>(
+    command: &'a mut CreateApplicationCommand,
+    registry: &ProviderRegistry,
+) -> &'a mut CreateApplicationCommand {
     command
         .name("ms")
         .description("Shows details about a Minesweeper Game")
         .create_option(|option| {
             option
                 .name("game_id")
-                .description("The GameID of the Minesweeper round")
+                .description("The GameID of the Minesweeper round (Default: your last game)")
                 .kind(CommandOptionType::String)
-                .required(true)
+                .set_autocomplete(true)
+                .required(false)
         })
         .create_option(|option| {
             option
@@ -360,16 +650,34 @@ pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicatio
             option
                 .name("provider")
                 .description(format!(
-                    "Where the game was played (Default: {DEFAULT_PROVIDER})"
+                    "Where the game was played (Default: {})",
+                    registry.default_provider().name()
                 ))
                 .kind(CommandOptionType::String)
-                .add_string_choice("Greev", DEFAULT_PROVIDER_GREEV)
-                .add_string_choice("McPlayHD", DEFAULT_PROVIDER_MCPLAY)
+                .required(false);
+            provider_choices(option, registry)
+        })
+        .create_option(|option| {
+            option
+                .name("rawvf")
+                .description("Attach the replay as .rawvf file for desktop replay viewers")
+                .kind(CommandOptionType::Boolean)
+                .required(false)
+        })
+        .create_option(|option| {
+            option
+                .name("file")
+                .description("Render a replay file instead (.avf, .rmv or .txt with game data)")
+                .kind(CommandOptionType::Attachment)
                 .required(false)
         })
 }
 
-async fn error_response(command: &ApplicationCommandInteraction, ctx: &Context, error_text: &str) {
+pub(crate) async fn error_response(
+    command: &ApplicationCommandInteraction,
+    ctx: &Context,
+    error_text: &str,
+) {
     command
         .create_followup_message(&ctx.http, |message| {
             message
@@ -0,0 +1,86 @@
+use serenity::builder::CreateApplicationCommand;
+use serenity::client::Context;
+use serenity::model::application::command::CommandType;
+use serenity::model::application::interaction::application_command::{
+    ApplicationCommandInteraction, ResolvedTarget,
+};
+use serenity::model::channel::Message;
+
+use crate::commands::render::{
+    create_game_embed, error_response, game_attachment, render_game, save_to_history,
+};
+use crate::minesweeper::provider::provider::Provider;
+use crate::minesweeper::provider::registry::{ProviderRegistry, get_registry};
+
+pub(crate) async fn run(command: &ApplicationCommandInteraction, ctx: &Context) {
+    let Some(ResolvedTarget::Message(message)) = command.data.target() else {
+        error_response(command, ctx, "Unable to read the message").await;
+        return;
+    };
+
+    let registry = get_registry(ctx).await;
+    let Some((provider, game_id)) = find_game(&registry, &message) else {
+        error_response(command, ctx, "The message does not contain a game id").await;
+        return;
+    };
+
+    let rendered_game = match render_game(ctx, provider, game_id.as_str(), &false, &false).await {
+        Ok(rendered_game) => rendered_game,
+        Err(error) => {
+            error_response(command, ctx, error.to_string().as_str()).await;
+            return;
+        }
+    };
+
+    save_to_history(
+        ctx,
+        provider,
+        game_id.as_str(),
+        &rendered_game.api_data,
+        command.user.id,
+    )
+    .await;
+
+    let result = command
+        .create_followup_message(&ctx.http, |message| {
+            message.embed(|e| {
+                create_game_embed(
+                    e,
+                    &registry,
+                    provider,
+                    game_id.as_str(),
+                    &rendered_game.api_data,
+                    &rendered_game.player_data,
+                    Some(&rendered_game.game_data),
+                )
+            });
+
+            message.add_file(game_attachment(&rendered_game.game_data, false));
+
+            message
+        })
+        .await;
+
+    if let Err(error) = result {
+        println!("Was unable to respond to command! {:?}", error)
+    }
+}
+
+/// Uses the first game id or link of any provider. As the user explicitly chose the message,
+/// a message consisting of nothing but an id is treated as a game of the default provider.
+fn find_game<'a>(
+    registry: &'a ProviderRegistry,
+    message: &Message,
+) -> Option<(&'a dyn Provider, String)> {
+    registry
+        .find_games(message.content.as_str())
+        .into_iter()
+        .next()
+        .or_else(|| registry.bare_game(message.content.as_str()))
+}
+
+pub fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
+    command
+        .name("Render Minesweeper game")
+        .kind(CommandType::Message)
+}
@@ -0,0 +1,134 @@
+use serenity::builder::CreateApplicationCommand;
+use serenity::client::Context;
+use serenity::model::Permissions;
+use serenity::model::application::command::CommandOptionType;
+use serenity::model::application::interaction::application_command::ApplicationCommandInteraction;
+use serenity::utils::Color;
+
+use crate::commands::render::{error_response, provider_choices};
+use crate::minesweeper::provider::registry::{ProviderRegistry, get_registry};
+use crate::storage::store::get_storage;
+use crate::storage::subscriptions::Subscription;
+
+pub(crate) async fn run(command: &ApplicationCommandInteraction, ctx: &Context) {
+    let option_provider = command.data.options.iter().find(|x| x.name.eq("provider"));
+    let option_personal_bests = command
+        .data
+        .options
+        .iter()
+        .find(|x| x.name.eq("personal_bests"));
+    let option_remove = command.data.options.iter().find(|x| x.name.eq("remove"));
+
+    let provider = option_provider
+        .and_then(|x| x.value.as_ref())
+        .and_then(|x| x.as_str())
+        .map(|x| x.to_lowercase());
+
+    let registry = get_registry(ctx).await;
+    let provider = match provider {
+        Some(provider) if registry.get(provider.as_str()).is_some() => provider,
+        _ => {
+            error_response(command, ctx, "Unknown Provider").await;
+            return;
+        }
+    };
+
+    let personal_bests = option_personal_bests
+        .map(|x| x.value.as_ref().unwrap().as_bool().unwrap_or(true))
+        .unwrap_or(true);
+
+    let remove = option_remove
+        .map(|x| x.value.as_ref().unwrap().as_bool().unwrap_or(false))
+        .unwrap_or(false);
+
+    //Subscriptions of providers which stopped listing their games can still be removed
+    let supported = registry
+        .get(provider.as_str())
+        .is_some_and(|x| x.supports_recent_games());
+    if !remove && !supported {
+        error_response(
+            command,
+            ctx,
+            "This provider does not list new games, so no records can be posted",
+        )
+        .await;
+        return;
+    }
+
+    let channel_id = command.channel_id.0;
+    let storage = get_storage(ctx).await;
+
+    let result = storage
+        .write(|data| {
+            data.subscriptions
+                .retain(|x| x.channel_id != channel_id || x.provider != provider);
+
+            if !remove {
+                data.subscriptions.push(Subscription {
+                    channel_id,
+                    provider: provider.clone(),
+                    personal_bests,
+                });
+            }
+        })
+        .await;
+
+    if result.is_err() {
+        error_response(command, ctx, "Unable to save the subscription").await;
+        return;
+    }
+
+    let description = if remove {
+        format!("New records from {provider} will no longer be posted in this channel")
+    } else if personal_bests {
+        format!("New top times and personal bests from {provider} will be posted in this channel")
+    } else {
+        format!("New top times from {provider} will be posted in this channel")
+    };
+
+    let result = command
+        .create_followup_message(&ctx.http, |message| {
+            message.embed(|e| {
+                e.description(description)
+                    .color(Color::from_rgb(102, 187, 106))
+            })
+        })
+        .await;
+
+    if let Err(error) = result {
+        println!("Was unable to respond to command! {:?}", error)
+    }
+}
+
+pub fn register<'a>(
+    command: &'a mut CreateApplicationCommand,
+    registry: &ProviderRegistry,
+) -> &'a mut CreateApplicationCommand {
+    command
+        .name("ms-subscribe")
+        .description("Automatically post new Minesweeper records in this channel")
+        .dm_permission(false)
+        .default_member_permissions(Permissions::MANAGE_CHANNELS)
+        .create_option(|option| {
+            option
+                .name("provider")
+                .description("Which server to watch for new records")
+                .kind(CommandOptionType::String)
+                .required(true);
+            provider_choices(option, registry)
+        })
+        .create_option(|option| {
+            option
+                .name("personal_bests")
+                .description("Also post personal bests, not only top times (Default: true)")
+                .kind(CommandOptionType::Boolean)
+                .required(false)
+        })
+        .create_option(|option| {
+            option
+                .name("remove")
+                .description("Stop posting records of this provider in this channel")
+                .kind(CommandOptionType::Boolean)
+                .required(false)
+        })
+}
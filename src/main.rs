@@ -1,48 +1,129 @@
+use std::collections::HashMap;
 use std::env;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use serenity::async_trait;
 use serenity::model::application::command::Command;
 use serenity::model::application::interaction::application_command::ApplicationCommandInteraction;
-use serenity::model::application::interaction::{Interaction, InteractionResponseType};
+use serenity::model::application::interaction::{
+    Interaction, InteractionResponseType, MessageFlags,
+};
+use serenity::model::channel::Message;
 use serenity::model::gateway::Ready;
 use serenity::prelude::*;
 
+use crate::commands::auto_render::AutoRenderCooldowns;
+use crate::commands::play::PlaySessions;
+use crate::minesweeper::provider::registry::{ProviderRegistry, ProviderRegistryKey, get_registry};
+use crate::storage::store::{Storage, StorageKey};
+
+mod cli;
 mod commands;
 mod minesweeper;
+mod storage;
+mod subscription;
 
-struct Handler;
+struct Handler {
+    poller_started: AtomicBool,
+}
 
 #[async_trait]
 impl EventHandler for Handler {
     async fn ready(&self, ctx: Context, ready: Ready) {
         println!("{} is connected!", ready.user.name);
 
-        let guild_command = Command::create_global_application_command(&ctx.http, |command| {
-            commands::render::register(command)
+        let registry = get_registry(&ctx).await;
+        let global_commands = Command::set_global_application_commands(&ctx.http, |commands| {
+            commands
+                .create_application_command(|command| {
+                    commands::render::register(command, &registry)
+                })
+                .create_application_command(|command| {
+                    commands::subscribe::register(command, &registry)
+                })
+                .create_application_command(|command| commands::auto_render::register(command))
+                .create_application_command(|command| commands::render_message::register(command))
+                .create_application_command(|command| commands::link::register(command))
+                .create_application_command(|command| commands::play::register(command))
+                .create_application_command(|command| commands::daily::register(command))
+                .create_application_command(|command| {
+                    commands::continue_game::register(command, &registry)
+                })
+                .create_application_command(|command| commands::audit::register(command, &registry))
         })
         .await;
 
         println!(
-            "I created the following global slash command: {:#?}",
-            guild_command
+            "I created the following global slash commands: {:#?}",
+            global_commands
         );
+
+        //Ready is sent again after a reconnect, the poller should only run once
+        if !self.poller_started.swap(true, Ordering::SeqCst) {
+            subscription::poller::start(ctx);
+        }
+    }
+
+    async fn message(&self, ctx: Context, new_message: Message) {
+        commands::auto_render::on_message(&ctx, &new_message).await;
     }
 
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
+        if let Interaction::Autocomplete(autocomplete) = &interaction {
+            if matches!(autocomplete.data.name.as_str(), "ms" | "ms-continue") {
+                commands::render::autocomplete(autocomplete, &ctx).await;
+            }
+            return;
+        }
+
+        if let Interaction::MessageComponent(component) = &interaction {
+            if component.data.custom_id.starts_with("ms-play:") {
+                commands::play::on_component(component, &ctx).await;
+            }
+            return;
+        }
+
+        if let Interaction::ModalSubmit(modal) = &interaction {
+            if modal.data.custom_id.starts_with("ms-play:") {
+                commands::play::on_modal_submit(modal, &ctx).await;
+            }
+            return;
+        }
+
         if let Interaction::ApplicationCommand(command) = interaction {
             ack(&command, &ctx).await;
             match command.data.name.as_str() {
                 "ms" => commands::render::run(&command, &ctx).await,
+                "ms-subscribe" => commands::subscribe::run(&command, &ctx).await,
+                "ms-autorender" => commands::auto_render::run(&command, &ctx).await,
+                "ms-link" => commands::link::run(&command, &ctx).await,
+                "ms-play" => commands::play::run(&command, &ctx).await,
+                "ms-daily" => commands::daily::run(&command, &ctx).await,
+                "ms-continue" => commands::continue_game::run(&command, &ctx).await,
+                "ms-audit" => commands::audit::run(&command, &ctx).await,
+                "Render Minesweeper game" => commands::render_message::run(&command, &ctx).await,
                 _ => unreachable!(),
             }
         }
     }
 }
 
+/// Commands answering with something only the user may see defer ephemerally,
+/// as the flags of the first followup can not change the visibility anymore.
 async fn ack(command: &ApplicationCommandInteraction, ctx: &Context) {
+    let ephemeral = matches!(command.data.name.as_str(), "ms-link" | "ms-audit");
+
     command
         .create_interaction_response(&ctx.http, |response| {
-            response.kind(InteractionResponseType::DeferredChannelMessageWithSource)
+            response
+                .kind(InteractionResponseType::DeferredChannelMessageWithSource)
+                .interaction_response_data(|data| {
+                    if ephemeral {
+                        data.flags(MessageFlags::EPHEMERAL);
+                    }
+                    data
+                })
         })
         .await
         .unwrap();
@@ -50,13 +131,33 @@ async fn ack(command: &ApplicationCommandInteraction, ctx: &Context) {
 
 #[tokio::main]
 async fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        std::process::exit(cli::run(&args));
+    }
+
     let token = env::var("DISCORD_TOKEN").expect("Expected a token in the environment");
+    let storage = Storage::load().expect("Unable to load the storage");
+    let registry = ProviderRegistry::load().expect("Unable to load the providers");
+
+    //Message content is only needed to find game ids for guilds which enabled auto rendering
+    let intents = GatewayIntents::GUILD_MESSAGES | GatewayIntents::MESSAGE_CONTENT;
 
-    let mut client = Client::builder(token, GatewayIntents::empty())
-        .event_handler(Handler)
+    let mut client = Client::builder(token, intents)
+        .event_handler(Handler {
+            poller_started: AtomicBool::new(false),
+        })
         .await
         .expect("Error creating client");
 
+    {
+        let mut data = client.data.write().await;
+        data.insert::<StorageKey>(Arc::new(storage));
+        data.insert::<ProviderRegistryKey>(Arc::new(registry));
+        data.insert::<AutoRenderCooldowns>(HashMap::new());
+        data.insert::<PlaySessions>(HashMap::new());
+    }
+
     if let Err(why) = client.start().await {
         println!("Client error: {:?}", why);
     }
@@ -1,3 +1,5 @@
+use crate::minesweeper::error::MinesweeperError;
+
 const BASE: i64 = 36;
 const CHARACTERS: &str = "0123456789abcdefghijklmnopqrstuvwxyz";
 
@@ -14,23 +16,16 @@ pub fn encode(number: i64) -> String {
     result
 }
 
-pub fn decode(number: &str) -> i64 {
-    let mut result: i64 = 0;
-    let length = number.len();
-    let chars: Vec<char> = CHARACTERS.chars().collect();
-
-    for i in 0..length {
-        let digit = chars
-            .iter()
-            .position(|&c| {
-                c == number
-                    .chars()
-                    .nth(length - i - 1)
-                    .expect("Error while decoding base36")
-            })
-            .expect("Error while decoding base36") as i64;
-        result += BASE.pow(i as u32) * digit;
-    }
+/// Game ids are typed in by users, so other characters and ids which do not fit are errors.
+pub fn decode(number: &str) -> Result<i64, MinesweeperError> {
+    number.chars().try_fold(0_i64, |result, character| {
+        let digit = CHARACTERS
+            .find(character)
+            .ok_or(MinesweeperError::GameDataNotFound)? as i64;
 
-    result
+        result
+            .checked_mul(BASE)
+            .and_then(|result| result.checked_add(digit))
+            .ok_or(MinesweeperError::GameDataNotFound)
+    })
 }
@@ -1,23 +1,36 @@
+use crate::minesweeper::error::MinesweeperError;
+
 const BASE: i64 = 62;
 const CHARACTERS: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
 
-pub fn decode(number: &str) -> i64 {
-    let mut result: i64 = 0;
-    let length = number.len();
-    let chars: Vec<char> = CHARACTERS.chars().collect();
+pub fn encode(number: i64) -> String {
+    if number == 0 {
+        return "0".to_string();
+    }
+
+    let mut result = String::with_capacity(1);
+    let mut num = number;
 
-    for i in 0..length {
-        let digit = chars
-            .iter()
-            .position(|&c| {
-                c == number
-                    .chars()
-                    .nth(length - i - 1)
-                    .expect("Error while decoding base62")
-            })
-            .expect("Error while decoding base62") as i64;
-        result += BASE.pow(i as u32) * digit;
+    while num > 0 {
+        let digit = num % BASE;
+        num /= BASE;
+        result.insert(0, CHARACTERS.chars().nth(digit as usize).unwrap());
     }
 
     result
 }
+
+/// Game data comes from providers and users, so other characters and numbers which do not fit
+/// are errors instead of panics. An empty string is 0.
+pub fn decode(number: &str) -> Result<i64, MinesweeperError> {
+    number.chars().try_fold(0_i64, |result, character| {
+        let digit = CHARACTERS
+            .find(character)
+            .ok_or(MinesweeperError::ApiDataParse)? as i64;
+
+        result
+            .checked_mul(BASE)
+            .and_then(|result| result.checked_add(digit))
+            .ok_or(MinesweeperError::ApiDataParse)
+    })
+}
@@ -0,0 +1,16 @@
+/// Formats the length of a game, e.g. `12.345s`, `1:23.456` or `26:01:23.456`.
+/// Hours are not wrapped, so games over a day stay readable.
+pub fn format_duration(millis: u64) -> String {
+    let hours = millis / 3_600_000;
+    let minutes = millis / 60_000 % 60;
+    let seconds = millis / 1000 % 60;
+    let millis = millis % 1000;
+
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}.{millis:03}")
+    } else if minutes > 0 {
+        format!("{minutes}:{seconds:02}.{millis:03}")
+    } else {
+        format!("{seconds}.{millis:03}s")
+    }
+}
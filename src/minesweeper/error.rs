@@ -12,8 +12,36 @@ pub enum MinesweeperError {
     ApiDataParse,
     #[error("Gamedata not found")]
     GameDataNotFound,
-    #[error("Data could not be parsed")]
-    DataParseError,
+    #[error("The provider did not accept the API key")]
+    Unauthorized,
+    #[error("Too many requests were sent to the provider")]
+    RateLimited,
+    #[error("The API of the provider is down")]
+    UpstreamDown,
     #[error("No Api Key was found for the provider")]
     ApiKeyNotFound,
+    #[error("The provider does not support this")]
+    Unsupported,
+    #[error("The board size is not supported")]
+    InvalidBoardSize,
+    #[error("There are too many mines for this board")]
+    TooManyMines,
+    #[error("The position is outside of the board")]
+    InvalidPosition,
+    #[error("No board matching the settings could be generated")]
+    NoBoardFound,
+    #[error("No-guess boards can be up to 30x30 with at most 25% mines")]
+    NoGuessTooLarge,
+    #[error("The game was not lost")]
+    NotLost,
+    #[error("The game data is from an unsupported version")]
+    UnsupportedVersion,
+    #[error("The header of the game data is invalid")]
+    InvalidHeader,
+    #[error("Only .avf, .rmv and .txt replays are supported")]
+    UnsupportedReplayFile,
+    #[error("The replay file is invalid")]
+    InvalidReplayFile,
+    #[error("The provider config is invalid")]
+    InvalidProviderConfig,
 }
@@ -0,0 +1,115 @@
+use std::fmt::Write;
+
+use crate::minesweeper::importers::mouse::SQUARE_SIZE;
+use crate::minesweeper::minesweeper_logic::{Board, FieldState};
+use crate::minesweeper::parsers::parser::{Action, ParsedData, TimedAction};
+
+/// Writes a game as RAW Minesweeper Video Format (Rev5), the text format desktop viewers convert from.
+///
+/// The header is a list of `Key: Value` lines, followed by the board with `*` for mines and `0`
+/// for safe fields and the mouse events as `<seconds> <event> <x> <y> (<pixel x> <pixel y>)`
+/// with one based fields. Every action is written as press and release of its mouse button:
+/// `lc`/`lr` opens, `rc`/`rr` flags and `mc`/`mr` chords.
+pub fn write(data: &ParsedData, player: &str) -> String {
+    let metadata = &data.metadata;
+    let mut board = data.game_board.clone();
+
+    let mut body = String::new();
+    let mut end_time = 0.0;
+    let mut result = "Unfinished";
+
+    for action in data.timeline() {
+        let seconds = action.total_time() as f64 * metadata.timeunits as f64 / 1000.0;
+        let (x, y) = action.position();
+        let (press, release) = match action {
+            TimedAction::Flag(_) => ("rc", "rr"),
+            TimedAction::Open(_) => ("lc", "lr"),
+            TimedAction::Chord(_) => ("mc", "mr"),
+        };
+
+        // Marks are off in the export, unsure flags only change the board
+        let unsure =
+            matches!(action, TimedAction::Flag(flag) if matches!(flag.action, Action::Toggle));
+
+        if !unsure {
+            for button in [press, release] {
+                let _ = writeln!(
+                    body,
+                    "{seconds:.3} {button} {} {} ({} {})",
+                    x + 1,
+                    y + 1,
+                    x * SQUARE_SIZE + SQUARE_SIZE / 2,
+                    y * SQUARE_SIZE + SQUARE_SIZE / 2
+                );
+            }
+        }
+
+        end_time = seconds;
+        match action {
+            TimedAction::Flag(flag) => flag.perform_action(&mut board),
+            TimedAction::Open(_) => board.open_field(x as usize, y as usize),
+            TimedAction::Chord(_) => {
+                board.chord(x as usize, y as usize);
+            }
+        }
+
+        if hit_mine(&board) {
+            result = "Lost";
+            let _ = writeln!(body, "{seconds:.3} blast");
+            break;
+        }
+        if board.open_fields == board.total_fields - board.mine_count {
+            result = "Won";
+            let _ = writeln!(body, "{seconds:.3} won");
+            break;
+        }
+    }
+
+    let mut output = String::new();
+    let _ = writeln!(output, "RawVF_Version: Rev5");
+    let _ = writeln!(output, "Program: RustyMsRenderBot");
+    let _ = writeln!(output, "Player: {player}");
+    let _ = writeln!(output, "Level: {}", level(&data.game_board));
+    let _ = writeln!(output, "Width: {}", metadata.x_size);
+    let _ = writeln!(output, "Height: {}", metadata.y_size);
+    let _ = writeln!(output, "Mines: {}", data.game_board.mine_count);
+    let _ = writeln!(output, "Marks: Off");
+    let _ = writeln!(output, "Time: {end_time:.3}");
+    let _ = writeln!(output, "Result: {result}");
+
+    let _ = writeln!(output, "Board:");
+    for row in data.game_board.fields.iter() {
+        let line: String = row
+            .iter()
+            .map(|field| if field.mine { '*' } else { '0' })
+            .collect();
+        let _ = writeln!(output, "{line}");
+    }
+
+    let _ = writeln!(output, "Events:");
+    let _ = writeln!(output, "0.000 start");
+    output.push_str(&body);
+
+    output
+}
+
+fn hit_mine(board: &Board) -> bool {
+    board
+        .fields
+        .iter()
+        .flatten()
+        .any(|field| field.mine && field.field_state == FieldState::Open)
+}
+
+fn level(board: &Board) -> &'static str {
+    match (
+        board.metadata.x_size,
+        board.metadata.y_size,
+        board.mine_count,
+    ) {
+        (8, 8, 10) => "Beginner",
+        (16, 16, 40) => "Intermediate",
+        (30, 16, 99) => "Expert",
+        _ => "Custom",
+    }
+}
@@ -1,9 +1,9 @@
 #[derive(Clone)]
 pub struct GameData {
     pub image_data: Vec<u8>,
-    pub total_actions: u8,
-    pub opened_fields: u8,
-    pub closed_fields: u8,
-    pub total_fields: u8,
-    pub mine_count: u8,
+    pub opened_fields: u32,
+    pub total_fields: u32,
+    pub mine_count: u32,
+    /// Drawn from the summary of the provider because the game has no replay, opened fields are unknown
+    pub summary: bool,
 }
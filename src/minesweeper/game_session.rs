@@ -0,0 +1,351 @@
+use crate::minesweeper::error::MinesweeperError;
+use crate::minesweeper::generator::generator::{BoardSettings, GeneratorType, generate};
+use crate::minesweeper::minesweeper_logic::{Board, FieldState};
+use crate::minesweeper::parsers::parser::{Action, ChordAction, FlagAction, Metadata, OpenAction};
+use crate::minesweeper::parsers::v3::header::Header;
+use crate::minesweeper::parsers::v3::writer;
+
+/// Milliseconds per time unit of recorded games.
+const TIMEUNITS: i32 = 10;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GameState {
+    Running,
+    Won,
+    Lost,
+}
+
+/// A game played inside Discord, recorded so it can be rendered like any other game.
+#[derive(Clone)]
+pub struct GameSession {
+    pub board: Board,
+    pub state: GameState,
+    pub open_data: Vec<OpenAction>,
+    pub flag_data: Vec<FlagAction>,
+    pub chord_data: Vec<ChordAction>,
+    pub settings: BoardSettings,
+    /// Whether the board comes from the generator, continued games keep the board of the original
+    generated: bool,
+    mines_placed: bool,
+    /// Unix timestamp in milliseconds of the first action
+    started_at: Option<i64>,
+    /// Time units which passed before this session started, used when continuing a game
+    time_offset: i64,
+}
+
+impl GameSession {
+    /// Starts a game, unless the generator is purely random the mines are placed on the first open.
+    pub fn new(settings: BoardSettings) -> Result<GameSession, MinesweeperError> {
+        settings.validate()?;
+
+        let metadata = Metadata {
+            x_size: settings.x_size,
+            y_size: settings.y_size,
+            timeunits: TIMEUNITS,
+        };
+
+        let mines_placed = settings.generator_type == GeneratorType::Random;
+        let mut board = if mines_placed {
+            generate(&settings, None)?
+        } else {
+            Board::new(&metadata, &[])?
+        };
+        board.metadata = metadata;
+
+        Ok(GameSession {
+            board,
+            state: GameState::Running,
+            open_data: Vec::new(),
+            flag_data: Vec::new(),
+            chord_data: Vec::new(),
+            settings,
+            generated: true,
+            mines_placed,
+            started_at: None,
+            time_offset: 0,
+        })
+    }
+
+    /// Replays a lost game up to the open or chord which hit a mine, so it can be continued from there.
+    /// The actions before the loss are kept, the replay of the session contains the whole game.
+    pub fn before_loss(
+        mut board: Board,
+        open_data: &[OpenAction],
+        flag_data: &[FlagAction],
+        chord_data: &[ChordAction],
+    ) -> Result<GameSession, MinesweeperError> {
+        // Opens and chords in the order they happened, chords are marked with `true`
+        let mut actions: Vec<(i64, i32, i32, bool)> = open_data
+            .iter()
+            .map(|action| (action.total_time, action.x, action.y, false))
+            .chain(
+                chord_data
+                    .iter()
+                    .map(|action| (action.total_time, action.x, action.y, true)),
+            )
+            .collect();
+        actions.sort_by_key(|action| action.0);
+
+        let mut flag_data = flag_data.to_vec();
+        flag_data.sort_by_key(|action| action.total_time);
+        let mut flags = flag_data.into_iter().peekable();
+
+        let mut session = GameSession {
+            settings: BoardSettings {
+                x_size: board.metadata.x_size,
+                y_size: board.metadata.y_size,
+                mine_count: board.mine_count,
+                generator_type: GeneratorType::Random,
+                seed: 0,
+            },
+            board: board.clone(),
+            state: GameState::Running,
+            open_data: Vec::new(),
+            flag_data: Vec::new(),
+            chord_data: Vec::new(),
+            generated: false,
+            mines_placed: true,
+            started_at: None,
+            time_offset: 0,
+        };
+
+        for (total_time, x, y, chord) in actions {
+            // Flags are performed before opens of the same time, like in the renderer
+            while let Some(flag) = flags.next_if(|flag| flag.total_time <= total_time) {
+                flag.perform_action(&mut board);
+                session.flag_data.push(flag);
+            }
+
+            let before = board.clone();
+            let opened = if chord {
+                board.chord(x as usize, y as usize)
+            } else {
+                board.open_field(x as usize, y as usize);
+                vec![(x as usize, y as usize)]
+            };
+
+            let hit_mine = opened.iter().any(|&(x, y)| {
+                board.fields[y][x].mine && board.fields[y][x].field_state == FieldState::Open
+            });
+
+            if hit_mine {
+                session.board = before;
+                session.time_offset = total_time;
+                session.recalculate_times();
+                return Ok(session);
+            }
+
+            if chord {
+                session.chord_data.push(ChordAction {
+                    x,
+                    y,
+                    time: 0,
+                    total_time,
+                });
+            } else {
+                session.open_data.push(OpenAction {
+                    x,
+                    y,
+                    time: 0,
+                    total_time,
+                });
+            }
+        }
+
+        Err(MinesweeperError::NotLost)
+    }
+
+    pub fn metadata(&self) -> &Metadata {
+        &self.board.metadata
+    }
+
+    pub fn mine_count(&self) -> u32 {
+        self.settings.mine_count
+    }
+
+    /// Milliseconds since the first action.
+    pub fn elapsed(&self, now: i64) -> i64 {
+        self.time_offset * self.timeunits() + self.started_at.map(|start| now - start).unwrap_or(0)
+    }
+
+    /// Milliseconds from the first to the last recorded action.
+    pub fn duration(&self) -> i64 {
+        let last_open = self.open_data.last().map(|x| x.total_time).unwrap_or(0);
+        let last_flag = self.flag_data.last().map(|x| x.total_time).unwrap_or(0);
+        let last_chord = self.chord_data.last().map(|x| x.total_time).unwrap_or(0);
+        last_open.max(last_flag).max(last_chord) * self.timeunits()
+    }
+
+    /// Whether the mines are not placed yet, which happens on the first open.
+    pub fn needs_board(&self) -> bool {
+        !self.mines_placed
+    }
+
+    /// Uses a board generated from the settings of the session before the first open.
+    /// No-guess boards can take a while, so they can be generated outside of [`GameSession::open`].
+    pub fn place_board(&mut self, mut board: Board) {
+        board.metadata = self.board.metadata.clone();
+        self.board = board;
+        self.restore_flags();
+        self.mines_placed = true;
+    }
+
+    pub fn open(&mut self, x: i32, y: i32, now: i64) -> Result<(), MinesweeperError> {
+        if self.state != GameState::Running {
+            return Ok(());
+        }
+
+        if !self.mines_placed {
+            let board = generate(&self.settings, Some((x, y)))?;
+            self.place_board(board);
+        }
+
+        let field = self.board.field(x, y)?;
+        if field.field_state != FieldState::Closed {
+            return Ok(());
+        }
+
+        let total_time = self.total_time(now);
+        let time = total_time - self.open_data.last().map(|x| x.total_time).unwrap_or(0);
+        self.open_data.push(OpenAction {
+            x,
+            y,
+            time,
+            total_time,
+        });
+
+        self.board.open_field(x as usize, y as usize);
+        self.update_state(&[(x as usize, y as usize)]);
+
+        Ok(())
+    }
+
+    /// Opens the neighbours of an open number if the right amount of flags is placed around it.
+    pub fn chord(&mut self, x: i32, y: i32, now: i64) {
+        if self.state != GameState::Running {
+            return;
+        }
+
+        let opened = self.board.chord(x as usize, y as usize);
+        if opened.is_empty() {
+            return;
+        }
+
+        let total_time = self.total_time(now);
+        let time = total_time - self.chord_data.last().map(|x| x.total_time).unwrap_or(0);
+        self.chord_data.push(ChordAction {
+            x,
+            y,
+            time,
+            total_time,
+        });
+
+        self.update_state(&opened);
+    }
+
+    /// Places a flag on a closed field or removes an existing one.
+    pub fn toggle_flag(&mut self, x: i32, y: i32, now: i64) {
+        if self.state != GameState::Running {
+            return;
+        }
+
+        let Ok(field) = self.board.field(x, y) else {
+            return;
+        };
+
+        let action = match field.field_state {
+            FieldState::Closed => Action::Place,
+            FieldState::Flagged | FieldState::UnsureFlagged => Action::Remove,
+            FieldState::Open => return,
+        };
+
+        let total_time = self.total_time(now);
+        let time = total_time - self.flag_data.last().map(|x| x.total_time).unwrap_or(0);
+        let flag_action = FlagAction {
+            x,
+            y,
+            time,
+            action,
+            total_time,
+        };
+
+        flag_action.perform_action(&mut self.board);
+        self.flag_data.push(flag_action);
+    }
+
+    pub fn flag_count(&self) -> usize {
+        self.board
+            .fields
+            .iter()
+            .flatten()
+            .filter(|field| field.field_state == FieldState::Flagged)
+            .count()
+    }
+
+    /// The game in the v3 format, which can be rendered with `/ms` like any other game.
+    pub fn to_replay(&self) -> String {
+        let header = Header {
+            metadata: self.metadata().clone(),
+            mine_count: Some(self.board.mine_count),
+            generator: self.generated.then_some(self.settings.generator_type),
+            seed: self.generated.then_some(self.settings.seed),
+            first_click: self.open_data.first().map(|action| (action.x, action.y)),
+            result: Some(self.state),
+            chords: true,
+        };
+
+        writer::write(
+            &header,
+            &self.board.mine_locations(),
+            &self.open_data,
+            &self.flag_data,
+            &self.chord_data,
+        )
+    }
+
+    fn update_state(&mut self, opened: &[(usize, usize)]) {
+        if opened.iter().any(|&(x, y)| self.board.fields[y][x].mine) {
+            self.state = GameState::Lost;
+        } else if self.board.open_fields == self.board.total_fields - self.board.mine_count {
+            self.state = GameState::Won;
+        }
+    }
+
+    fn total_time(&mut self, now: i64) -> i64 {
+        let started_at = *self.started_at.get_or_insert(now);
+        self.time_offset + (now - started_at) / self.timeunits()
+    }
+
+    /// Continued games keep the time units of the original game.
+    fn timeunits(&self) -> i64 {
+        self.board.metadata.timeunits.max(1) as i64
+    }
+
+    /// Times are stored relative to the previous action of the same kind.
+    fn recalculate_times(&mut self) {
+        let mut previous = 0;
+        for action in self.open_data.iter_mut() {
+            action.time = action.total_time - previous;
+            previous = action.total_time;
+        }
+
+        let mut previous = 0;
+        for action in self.flag_data.iter_mut() {
+            action.time = action.total_time - previous;
+            previous = action.total_time;
+        }
+
+        let mut previous = 0;
+        for action in self.chord_data.iter_mut() {
+            action.time = action.total_time - previous;
+            previous = action.total_time;
+        }
+    }
+
+    /// Flags placed before the first open have to be copied onto the newly generated board.
+    fn restore_flags(&mut self) {
+        for action in self.flag_data.iter() {
+            action.perform_action(&mut self.board);
+        }
+    }
+}
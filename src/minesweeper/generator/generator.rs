@@ -0,0 +1,216 @@
+use crate::minesweeper::error::MinesweeperError;
+use crate::minesweeper::generator::random::SeededRandom;
+use crate::minesweeper::generator::solver::is_solvable;
+use crate::minesweeper::minesweeper_logic::{Board, MAX_BOARD_SIZE};
+use crate::minesweeper::parsers::parser::Metadata;
+
+/// How many boards are tried before giving up on finding a no-guess board.
+const MAX_NO_GUESS_ATTEMPTS: u32 = 2000;
+/// The solver runs for every attempt, so no-guess boards are limited to keep generating fast.
+const MAX_NO_GUESS_SIZE: i32 = 30;
+/// Percent of the fields, dense boards rarely are solvable without guessing.
+const MAX_NO_GUESS_DENSITY: u32 = 25;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GeneratorType {
+    /// Mines are placed anywhere, the first click can hit one.
+    Random,
+    /// The first clicked field is never a mine.
+    FirstClickSafe,
+    /// The first clicked field and its neighbours are free, so the first click opens an area.
+    FirstClickOpening,
+    /// Like `FirstClickOpening`, but the board can be solved without guessing.
+    NoGuess,
+}
+
+impl GeneratorType {
+    pub const ALL: [GeneratorType; 4] = [
+        GeneratorType::Random,
+        GeneratorType::FirstClickSafe,
+        GeneratorType::FirstClickOpening,
+        GeneratorType::NoGuess,
+    ];
+
+    pub fn id(&self) -> &str {
+        match self {
+            GeneratorType::Random => "random",
+            GeneratorType::FirstClickSafe => "safe",
+            GeneratorType::FirstClickOpening => "opening",
+            GeneratorType::NoGuess => "no-guess",
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            GeneratorType::Random => "Random",
+            GeneratorType::FirstClickSafe => "Safe first click",
+            GeneratorType::FirstClickOpening => "Opening first click",
+            GeneratorType::NoGuess => "No guessing",
+        }
+    }
+}
+
+impl std::str::FromStr for GeneratorType {
+    type Err = MinesweeperError;
+
+    /// Accepts the ids used by the bot as well as the generator names sent by the Greev API.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().replace(['_', ' '], "-").as_str() {
+            "random" | "default" => Ok(GeneratorType::Random),
+            "safe" | "first-click-safe" | "safe-first-click" => Ok(GeneratorType::FirstClickSafe),
+            "opening" | "first-click-opening" | "opening-first-click" => {
+                Ok(GeneratorType::FirstClickOpening)
+            }
+            "no-guess" | "noguess" | "no-guessing" => Ok(GeneratorType::NoGuess),
+            _ => Err(MinesweeperError::Unsupported),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Difficulty {
+    Beginner,
+    Intermediate,
+    Expert,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [
+        Difficulty::Beginner,
+        Difficulty::Intermediate,
+        Difficulty::Expert,
+    ];
+
+    pub fn name(&self) -> &str {
+        match self {
+            Difficulty::Beginner => "Beginner",
+            Difficulty::Intermediate => "Intermediate",
+            Difficulty::Expert => "Expert",
+        }
+    }
+
+    /// Width, height and mine count of the board.
+    pub fn size(&self) -> (i32, i32, u32) {
+        match self {
+            Difficulty::Beginner => (9, 9, 10),
+            Difficulty::Intermediate => (16, 16, 40),
+            Difficulty::Expert => (30, 16, 99),
+        }
+    }
+}
+
+/// Everything needed to generate a board, the same settings always produce the same board.
+#[derive(Clone, Copy, Debug)]
+pub struct BoardSettings {
+    pub x_size: i32,
+    pub y_size: i32,
+    pub mine_count: u32,
+    pub generator_type: GeneratorType,
+    pub seed: u64,
+}
+
+impl BoardSettings {
+    pub fn validate(&self) -> Result<(), MinesweeperError> {
+        if self.x_size < 2
+            || self.y_size < 2
+            || self.x_size > MAX_BOARD_SIZE
+            || self.y_size > MAX_BOARD_SIZE
+        {
+            return Err(MinesweeperError::InvalidBoardSize);
+        }
+
+        let fields = (self.x_size * self.y_size) as u32;
+        let free_fields = match self.generator_type {
+            GeneratorType::Random => 0,
+            GeneratorType::FirstClickSafe => 1,
+            GeneratorType::FirstClickOpening | GeneratorType::NoGuess => 9,
+        };
+
+        if self.mine_count == 0
+            || self
+                .mine_count
+                .checked_add(free_fields)
+                .is_none_or(|needed| needed > fields)
+        {
+            return Err(MinesweeperError::TooManyMines);
+        }
+
+        if self.generator_type == GeneratorType::NoGuess
+            && (self.x_size > MAX_NO_GUESS_SIZE
+                || self.y_size > MAX_NO_GUESS_SIZE
+                || self
+                    .mine_count
+                    .checked_mul(100)
+                    .is_none_or(|percent| percent > fields * MAX_NO_GUESS_DENSITY))
+        {
+            return Err(MinesweeperError::NoGuessTooLarge);
+        }
+
+        Ok(())
+    }
+}
+
+/// Generates a board, the first click is needed by every generator except `Random`.
+pub fn generate(
+    settings: &BoardSettings,
+    first_click: Option<(i32, i32)>,
+) -> Result<Board, MinesweeperError> {
+    settings.validate()?;
+
+    let metadata = Metadata {
+        x_size: settings.x_size,
+        y_size: settings.y_size,
+        timeunits: 10,
+    };
+    let mut random = SeededRandom::new(settings.seed);
+
+    let first_click = match (settings.generator_type, first_click) {
+        (GeneratorType::Random, _) => None,
+        (_, Some((x, y))) if x >= 0 && x < settings.x_size && y >= 0 && y < settings.y_size => {
+            Some((x, y))
+        }
+        _ => return Err(MinesweeperError::InvalidPosition),
+    };
+
+    if settings.generator_type != GeneratorType::NoGuess {
+        let mines = place_mines(settings, first_click, &mut random);
+        return Board::new(&metadata, &mines);
+    }
+
+    let first_click = first_click.expect("No-guess boards always have a first click");
+    for _ in 0..MAX_NO_GUESS_ATTEMPTS {
+        let mines = place_mines(settings, Some(first_click), &mut random);
+        let board = Board::new(&metadata, &mines)?;
+
+        if is_solvable(&board, first_click) {
+            return Ok(board);
+        }
+    }
+
+    Err(MinesweeperError::NoBoardFound)
+}
+
+fn place_mines(
+    settings: &BoardSettings,
+    first_click: Option<(i32, i32)>,
+    random: &mut SeededRandom,
+) -> Vec<(i32, i32)> {
+    let mut candidates: Vec<(i32, i32)> = (0..settings.y_size)
+        .flat_map(|y| (0..settings.x_size).map(move |x| (x, y)))
+        .filter(|&(x, y)| match (settings.generator_type, first_click) {
+            (GeneratorType::FirstClickSafe, Some(click)) => (x, y) != click,
+            (GeneratorType::FirstClickOpening | GeneratorType::NoGuess, Some(click)) => {
+                (x - click.0).abs() > 1 || (y - click.1).abs() > 1
+            }
+            _ => true,
+        })
+        .collect();
+
+    let mut mines = Vec::with_capacity(settings.mine_count as usize);
+    for _ in 0..settings.mine_count {
+        let index = random.next_below(candidates.len());
+        mines.push(candidates.swap_remove(index));
+    }
+
+    mines
+}
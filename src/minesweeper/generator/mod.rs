@@ -0,0 +1,4 @@
+#[allow(clippy::module_inception)]
+pub mod generator;
+pub mod random;
+pub mod solver;
@@ -0,0 +1,228 @@
+use crate::minesweeper::minesweeper_logic::{Board, FieldState};
+
+/// Mines which still have to be found among the closed neighbours of an opened number.
+struct Constraint {
+    x: i32,
+    y: i32,
+    closed: Vec<(i32, i32)>,
+    mines: usize,
+}
+
+/// Whether the board can be cleared from the first click by logic alone, without ever guessing.
+pub fn is_solvable(board: &Board, first_click: (i32, i32)) -> bool {
+    let mut board = board.clone();
+    let (x, y) = first_click;
+
+    if board.fields[y as usize][x as usize].mine {
+        return false;
+    }
+
+    board.open_field(x as usize, y as usize);
+    let safe_fields = board.total_fields - board.mine_count;
+
+    while board.open_fields < safe_fields {
+        let progress = apply_single_constraints(&mut board)
+            || apply_constraint_pairs(&mut board)
+            || apply_mine_count(&mut board);
+
+        if !progress {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Closed fields which are known to be safe from the opened fields alone.
+/// Flags of the player are ignored, they might be wrong.
+pub fn deducible_safe_fields(board: &Board) -> Vec<(i32, i32)> {
+    let mut board = board.clone();
+    for field in board.fields.iter_mut().flatten() {
+        if field.field_state != FieldState::Open {
+            field.field_state = FieldState::Closed;
+        }
+    }
+
+    let closed = fields_with_state(&board, FieldState::Closed);
+    let safe_fields = board.total_fields - board.mine_count;
+
+    while board.open_fields < safe_fields
+        && (apply_single_constraints(&mut board)
+            || apply_constraint_pairs(&mut board)
+            || apply_mine_count(&mut board))
+    {}
+
+    closed
+        .into_iter()
+        .filter(|&(x, y)| board.fields[y as usize][x as usize].field_state == FieldState::Open)
+        .collect()
+}
+
+/// A number with as many flags around it as its value opens the other neighbours,
+/// one with as many closed neighbours as missing mines flags all of them.
+fn apply_single_constraints(board: &mut Board) -> bool {
+    let mut progress = false;
+
+    for constraint in constraints(board) {
+        if constraint.mines == 0 {
+            open_all(board, &constraint.closed);
+            progress = true;
+        } else if constraint.mines == constraint.closed.len() {
+            flag_all(board, &constraint.closed);
+            progress = true;
+        }
+    }
+
+    progress
+}
+
+/// If the closed neighbours of one number are a subset of another number's, the mines
+/// of the difference are known.
+fn apply_constraint_pairs(board: &mut Board) -> bool {
+    let constraints = constraints(board);
+    let mut progress = false;
+
+    for a in constraints.iter() {
+        for b in constraints.iter() {
+            if (a.x == b.x && a.y == b.y) || (a.x - b.x).abs() > 2 || (a.y - b.y).abs() > 2 {
+                continue;
+            }
+
+            if b.mines < a.mines || !a.closed.iter().all(|field| b.closed.contains(field)) {
+                continue;
+            }
+
+            let difference: Vec<(i32, i32)> = b
+                .closed
+                .iter()
+                .filter(|field| !a.closed.contains(field))
+                .copied()
+                .collect();
+            let mines = b.mines - a.mines;
+
+            if difference.is_empty() {
+                continue;
+            }
+
+            if mines == 0 {
+                open_all(board, &difference);
+                progress = true;
+            } else if mines == difference.len() {
+                flag_all(board, &difference);
+                progress = true;
+            }
+        }
+    }
+
+    progress
+}
+
+/// Once all mines are flagged the rest is safe, if only mines are left they are flagged.
+fn apply_mine_count(board: &mut Board) -> bool {
+    let closed: Vec<(i32, i32)> = fields_with_state(board, FieldState::Closed);
+    let flagged = fields_with_state(board, FieldState::Flagged).len() as u32;
+    let remaining_mines = board.mine_count.saturating_sub(flagged) as usize;
+
+    if closed.is_empty() {
+        return false;
+    }
+
+    if remaining_mines == 0 {
+        open_all(board, &closed);
+        return true;
+    }
+
+    if remaining_mines == closed.len() {
+        flag_all(board, &closed);
+        return true;
+    }
+
+    false
+}
+
+fn constraints(board: &Board) -> Vec<Constraint> {
+    let mut constraints = Vec::new();
+
+    for y in 0..board.metadata.y_size {
+        for x in 0..board.metadata.x_size {
+            let field = &board.fields[y as usize][x as usize];
+            if field.field_state != FieldState::Open || field.mine || field.value == 0 {
+                continue;
+            }
+
+            let mut closed = Vec::new();
+            let mut flagged = 0;
+            for (xx, yy) in neighbours(board, x, y) {
+                match board.fields[yy as usize][xx as usize].field_state {
+                    FieldState::Closed => closed.push((xx, yy)),
+                    FieldState::Flagged => flagged += 1,
+                    _ => {}
+                }
+            }
+
+            if closed.is_empty() {
+                continue;
+            }
+
+            constraints.push(Constraint {
+                x,
+                y,
+                closed,
+                mines: (field.value as usize).saturating_sub(flagged),
+            });
+        }
+    }
+
+    constraints
+}
+
+fn neighbours(board: &Board, x: i32, y: i32) -> Vec<(i32, i32)> {
+    let mut neighbours = Vec::with_capacity(8);
+
+    for xd in -1..=1_i32 {
+        for yd in -1..=1_i32 {
+            let xx = x + xd;
+            let yy = y + yd;
+            if xx < 0
+                || xx >= board.metadata.x_size
+                || yy < 0
+                || yy >= board.metadata.y_size
+                || (xd == 0 && yd == 0)
+            {
+                continue;
+            }
+            neighbours.push((xx, yy));
+        }
+    }
+
+    neighbours
+}
+
+fn fields_with_state(board: &Board, state: FieldState) -> Vec<(i32, i32)> {
+    let mut fields = Vec::new();
+
+    for (y, row) in board.fields.iter().enumerate() {
+        for (x, field) in row.iter().enumerate() {
+            if field.field_state == state {
+                fields.push((x as i32, y as i32));
+            }
+        }
+    }
+
+    fields
+}
+
+fn open_all(board: &mut Board, fields: &[(i32, i32)]) {
+    for &(x, y) in fields {
+        board.open_field(x as usize, y as usize);
+    }
+}
+
+fn flag_all(board: &mut Board, fields: &[(i32, i32)]) {
+    for &(x, y) in fields {
+        let field = &mut board.fields[y as usize][x as usize];
+        if field.field_state == FieldState::Closed {
+            field.field_state = FieldState::Flagged;
+        }
+    }
+}
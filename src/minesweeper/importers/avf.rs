@@ -0,0 +1,99 @@
+use crate::minesweeper::error::MinesweeperError;
+use crate::minesweeper::importers::ByteReader;
+use crate::minesweeper::importers::mouse::{MouseButton, MouseEvent, to_parsed_data};
+use crate::minesweeper::minesweeper_logic::{Board, MAX_BOARD_SIZE};
+use crate::minesweeper::parsers::parser::{Metadata, ParsedData};
+
+/// Every mouse event of an AVF file takes 8 bytes.
+const EVENT_SIZE: usize = 8;
+
+/// Parses a replay of Minesweeper Arbiter.
+///
+/// The file starts with the version, 4 unused bytes and the mode (3-5 for the standard
+/// difficulties, 6 for custom boards followed by width - 1, height - 1 and the mine count).
+/// The mines follow as one based row and column, then a text header with the player and the
+/// result and finally the mouse events.
+pub fn parse(data: &[u8]) -> Result<ParsedData, MinesweeperError> {
+    let mut reader = ByteReader::new(data);
+
+    reader.u8()?;
+    reader.bytes(4)?;
+
+    let (x_size, y_size, mine_count) = match reader.u8()? {
+        3 => (8, 8, 10),
+        4 => (16, 16, 40),
+        5 => (30, 16, 99),
+        6 => (
+            reader.u8()? as i32 + 1,
+            reader.u8()? as i32 + 1,
+            reader.u16()? as usize,
+        ),
+        _ => return Err(MinesweeperError::InvalidReplayFile),
+    };
+    if x_size > MAX_BOARD_SIZE || y_size > MAX_BOARD_SIZE {
+        return Err(MinesweeperError::InvalidBoardSize);
+    }
+
+    let mut mines = Vec::with_capacity(mine_count);
+    for _ in 0..mine_count {
+        let y = reader.u8()? as i32 - 1;
+        let x = reader.u8()? as i32 - 1;
+        mines.push((x, y));
+    }
+
+    let metadata = Metadata {
+        x_size,
+        y_size,
+        timeunits: 10,
+    };
+    let board = Board::new(&metadata, &mines).map_err(|_| MinesweeperError::InvalidReplayFile)?;
+
+    // The length of the text header is not stored, the events start with the first event at 0s
+    let start = (reader.offset..data.len().saturating_sub(EVENT_SIZE * 2))
+        .find(|&offset| {
+            is_first_event(&data[offset..offset + EVENT_SIZE])
+                && parse_event(&data[offset + EVENT_SIZE..offset + EVENT_SIZE * 2]).is_some()
+        })
+        .ok_or(MinesweeperError::InvalidReplayFile)?;
+
+    // The list of events ends with an event before the start of the game
+    let events: Vec<MouseEvent> = data[start..]
+        .chunks_exact(EVENT_SIZE)
+        .map_while(parse_event)
+        .collect();
+
+    Ok(to_parsed_data(board, &events))
+}
+
+fn is_first_event(bytes: &[u8]) -> bool {
+    bytes[2] == 1 && bytes[6] == 0 && bytes[4] < 100 && button(bytes[0]).is_some()
+}
+
+/// Layout of an event: button, x high, seconds low, x low, hundredths, y high, seconds high, y low.
+/// Seconds are stored with an offset of one.
+fn parse_event(bytes: &[u8]) -> Option<MouseEvent> {
+    let seconds = ((bytes[6] as i64) << 8 | bytes[2] as i64) - 1;
+    if seconds < 0 || bytes[4] >= 100 {
+        return None;
+    }
+
+    Some(MouseEvent {
+        button: button(bytes[0])?,
+        time: seconds * 1000 + bytes[4] as i64 * 10,
+        x: (bytes[1] as i32) << 8 | bytes[3] as i32,
+        y: (bytes[5] as i32) << 8 | bytes[7] as i32,
+    })
+}
+
+fn button(event: u8) -> Option<MouseButton> {
+    match event {
+        1 => Some(MouseButton::Move),
+        3 => Some(MouseButton::LeftDown),
+        5 | 21 => Some(MouseButton::LeftUp),
+        9 | 11 => Some(MouseButton::RightDown),
+        17 | 145 => Some(MouseButton::RightUp),
+        33 => Some(MouseButton::MiddleDown),
+        65 | 193 => Some(MouseButton::MiddleUp),
+        _ => None,
+    }
+}
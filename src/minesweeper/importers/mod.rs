@@ -0,0 +1,70 @@
+use crate::minesweeper::error::MinesweeperError;
+use crate::minesweeper::parsers;
+use crate::minesweeper::parsers::parser::ParsedData;
+
+pub mod avf;
+pub mod mouse;
+pub mod rmv;
+
+/// Imports a replay file by its extension, text files contain game data like the providers send it.
+pub fn import(file_name: &str, data: &[u8]) -> Result<ParsedData, MinesweeperError> {
+    let extension = file_name
+        .rsplit_once('.')
+        .map(|(_, extension)| extension.to_lowercase())
+        .unwrap_or_default();
+
+    match extension.as_str() {
+        "avf" => avf::parse(data),
+        "rmv" => rmv::parse(data),
+        "txt" => {
+            let game_data =
+                std::str::from_utf8(data).map_err(|_| MinesweeperError::InvalidReplayFile)?;
+            parsers::parse_game(game_data.trim())
+        }
+        _ => Err(MinesweeperError::UnsupportedReplayFile),
+    }
+}
+
+/// Reads big endian numbers from a replay file without panicking on truncated files.
+pub(crate) struct ByteReader<'a> {
+    data: &'a [u8],
+    pub offset: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    pub fn new(data: &'a [u8]) -> ByteReader<'a> {
+        ByteReader { data, offset: 0 }
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.data.len().saturating_sub(self.offset)
+    }
+
+    pub fn bytes(&mut self, count: usize) -> Result<&'a [u8], MinesweeperError> {
+        let bytes = self
+            .data
+            .get(self.offset..self.offset + count)
+            .ok_or(MinesweeperError::InvalidReplayFile)?;
+        self.offset += count;
+        Ok(bytes)
+    }
+
+    pub fn u8(&mut self) -> Result<u8, MinesweeperError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    pub fn u16(&mut self) -> Result<u16, MinesweeperError> {
+        let bytes = self.bytes(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    pub fn u24(&mut self) -> Result<u32, MinesweeperError> {
+        let bytes = self.bytes(3)?;
+        Ok(u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]))
+    }
+
+    pub fn u32(&mut self) -> Result<u32, MinesweeperError> {
+        let bytes = self.bytes(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+}
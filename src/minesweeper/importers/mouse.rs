@@ -0,0 +1,202 @@
+use crate::minesweeper::minesweeper_logic::{Board, FieldState};
+use crate::minesweeper::parsers::parser::{
+    Action, ChordAction, FlagAction, Metadata, OpenAction, ParsedData,
+};
+
+/// Pixels per field in the clones the replays are recorded with.
+pub const SQUARE_SIZE: i32 = 16;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MouseButton {
+    Move,
+    LeftDown,
+    LeftUp,
+    RightDown,
+    RightUp,
+    MiddleDown,
+    MiddleUp,
+}
+
+/// A mouse event of a replay, `x` and `y` are pixels relative to the top left of the board.
+#[derive(Clone, Copy, Debug)]
+pub struct MouseEvent {
+    pub button: MouseButton,
+    /// Milliseconds since the start of the game
+    pub time: i64,
+    pub x: i32,
+    pub y: i32,
+}
+
+/// Turns the mouse events of a classic clone into the actions of the game,
+/// left release opens, right press flags and releasing one button while both are down chords.
+pub fn to_parsed_data(board: Board, events: &[MouseEvent]) -> ParsedData {
+    let metadata = Metadata {
+        x_size: board.metadata.x_size,
+        y_size: board.metadata.y_size,
+        timeunits: 10,
+    };
+
+    let mut recorder = Recorder {
+        board: board.clone(),
+        open_data: Vec::new(),
+        flag_data: Vec::new(),
+        chord_data: Vec::new(),
+        finished: false,
+    };
+
+    let mut left = false;
+    let mut right = false;
+    let mut chorded = false;
+
+    for event in events {
+        if recorder.finished {
+            break;
+        }
+
+        let time = event.time / metadata.timeunits as i64;
+        let field = field_at(&metadata, event.x, event.y);
+
+        match event.button {
+            MouseButton::Move | MouseButton::MiddleDown => {}
+            MouseButton::LeftDown => left = true,
+            MouseButton::RightDown => {
+                right = true;
+                if !left {
+                    recorder.toggle_flag(field, time);
+                }
+            }
+            MouseButton::LeftUp => {
+                if right {
+                    recorder.chord(field, time);
+                    chorded = true;
+                } else if !chorded {
+                    recorder.open(field, time);
+                }
+                left = false;
+            }
+            MouseButton::RightUp => {
+                if left {
+                    recorder.chord(field, time);
+                    chorded = true;
+                }
+                right = false;
+            }
+            MouseButton::MiddleUp => recorder.chord(field, time),
+        }
+
+        if !left && !right {
+            chorded = false;
+        }
+    }
+
+    ParsedData {
+        metadata,
+        game_board: board,
+        open_data: with_deltas(recorder.open_data, |x| &mut x.total_time, |x| &mut x.time),
+        flag_data: with_deltas(recorder.flag_data, |x| &mut x.total_time, |x| &mut x.time),
+        chord_data: with_deltas(recorder.chord_data, |x| &mut x.total_time, |x| &mut x.time),
+    }
+}
+
+struct Recorder {
+    board: Board,
+    open_data: Vec<OpenAction>,
+    flag_data: Vec<FlagAction>,
+    chord_data: Vec<ChordAction>,
+    finished: bool,
+}
+
+impl Recorder {
+    fn open(&mut self, field: Option<(i32, i32)>, total_time: i64) {
+        let Some((x, y)) = field else {
+            return;
+        };
+
+        if self.board.fields[y as usize][x as usize].field_state != FieldState::Closed {
+            return;
+        }
+
+        self.board.open_field(x as usize, y as usize);
+        self.open_data.push(OpenAction {
+            x,
+            y,
+            time: 0,
+            total_time,
+        });
+        self.check_finished(&[(x as usize, y as usize)]);
+    }
+
+    fn toggle_flag(&mut self, field: Option<(i32, i32)>, total_time: i64) {
+        let Some((x, y)) = field else {
+            return;
+        };
+
+        let action = match self.board.fields[y as usize][x as usize].field_state {
+            FieldState::Closed => Action::Place,
+            FieldState::Flagged | FieldState::UnsureFlagged => Action::Remove,
+            FieldState::Open => return,
+        };
+
+        let flag_action = FlagAction {
+            x,
+            y,
+            time: 0,
+            action,
+            total_time,
+        };
+        flag_action.perform_action(&mut self.board);
+        self.flag_data.push(flag_action);
+    }
+
+    fn chord(&mut self, field: Option<(i32, i32)>, total_time: i64) {
+        let Some((x, y)) = field else {
+            return;
+        };
+
+        let opened = self.board.chord(x as usize, y as usize);
+        if opened.is_empty() {
+            return;
+        }
+
+        self.chord_data.push(ChordAction {
+            x,
+            y,
+            time: 0,
+            total_time,
+        });
+        self.check_finished(&opened);
+    }
+
+    fn check_finished(&mut self, opened: &[(usize, usize)]) {
+        self.finished = opened.iter().any(|&(x, y)| self.board.fields[y][x].mine)
+            || self.board.open_fields == self.board.total_fields - self.board.mine_count;
+    }
+}
+
+fn field_at(metadata: &Metadata, x: i32, y: i32) -> Option<(i32, i32)> {
+    if x < 0 || y < 0 {
+        return None;
+    }
+
+    let (x, y) = (x / SQUARE_SIZE, y / SQUARE_SIZE);
+    if x >= metadata.x_size || y >= metadata.y_size {
+        return None;
+    }
+
+    Some((x, y))
+}
+
+/// Times of the parsers are relative to the previous action of the same kind.
+fn with_deltas<T>(
+    mut actions: Vec<T>,
+    total_time: impl Fn(&mut T) -> &mut i64,
+    time: impl Fn(&mut T) -> &mut i64,
+) -> Vec<T> {
+    let mut previous = 0;
+    for action in actions.iter_mut() {
+        let current = *total_time(action);
+        *time(action) = current - previous;
+        previous = current;
+    }
+    actions
+}
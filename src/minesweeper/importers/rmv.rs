@@ -0,0 +1,96 @@
+use crate::minesweeper::error::MinesweeperError;
+use crate::minesweeper::importers::ByteReader;
+use crate::minesweeper::importers::mouse::{MouseButton, MouseEvent, to_parsed_data};
+use crate::minesweeper::minesweeper_logic::{Board, MAX_BOARD_SIZE};
+use crate::minesweeper::parsers::parser::{Metadata, ParsedData};
+
+const MAGIC: &[u8] = b"*rmv";
+
+/// Parses a replay of Viennasweeper.
+///
+/// After the magic and the format version the header lists the sizes of the sections:
+/// result, version, player info, board, preflags, properties, video and checksum.
+/// Only the board and the mouse events of the video are needed for rendering.
+pub fn parse(data: &[u8]) -> Result<ParsedData, MinesweeperError> {
+    let mut reader = ByteReader::new(data);
+
+    if reader.bytes(MAGIC.len())? != MAGIC {
+        return Err(MinesweeperError::InvalidReplayFile);
+    }
+
+    reader.u16()?;
+    let result_size = reader.u16()? as usize;
+    let version_size = reader.u16()? as usize;
+    let player_size = reader.u16()? as usize;
+    let board_size = reader.u16()? as usize;
+    let preflags_size = reader.u16()? as usize;
+    let properties_size = reader.u16()? as usize;
+    let video_size = reader.u32()? as usize;
+    reader.u16()?;
+
+    reader.bytes(result_size + version_size + player_size)?;
+
+    let mut board_reader = ByteReader::new(reader.bytes(board_size)?);
+    board_reader.u32()?;
+    let x_size = board_reader.u8()? as i32;
+    let y_size = board_reader.u8()? as i32;
+    let mine_count = board_reader.u16()? as usize;
+    if x_size > MAX_BOARD_SIZE || y_size > MAX_BOARD_SIZE {
+        return Err(MinesweeperError::InvalidBoardSize);
+    }
+
+    let mut mines = Vec::with_capacity(mine_count);
+    for _ in 0..mine_count {
+        let x = board_reader.u8()? as i32;
+        let y = board_reader.u8()? as i32;
+        mines.push((x, y));
+    }
+
+    let metadata = Metadata {
+        x_size,
+        y_size,
+        timeunits: 10,
+    };
+    let board = Board::new(&metadata, &mines).map_err(|_| MinesweeperError::InvalidReplayFile)?;
+
+    reader.bytes(preflags_size + properties_size)?;
+    let events = parse_events(&mut ByteReader::new(reader.bytes(video_size)?))?;
+
+    Ok(to_parsed_data(board, &events))
+}
+
+/// Mouse events are followed by the time in milliseconds and the position,
+/// board events by the field and the events which end the game have no data.
+fn parse_events(reader: &mut ByteReader) -> Result<Vec<MouseEvent>, MinesweeperError> {
+    let mut events = Vec::new();
+
+    while reader.remaining() > 0 {
+        let button = match reader.u8()? {
+            0 => {
+                reader.bytes(4)?;
+                continue;
+            }
+            1 => MouseButton::Move,
+            2 => MouseButton::LeftDown,
+            3 => MouseButton::LeftUp,
+            4 => MouseButton::RightDown,
+            5 => MouseButton::RightUp,
+            6 => MouseButton::MiddleDown,
+            7 => MouseButton::MiddleUp,
+            9..=14 | 18..=27 => {
+                reader.bytes(2)?;
+                continue;
+            }
+            15..=17 => break,
+            _ => return Err(MinesweeperError::InvalidReplayFile),
+        };
+
+        let time = reader.u24()? as i64;
+        let x = reader.u16()? as i32;
+        let y = reader.u16()? as i32;
+
+        events.push(MouseEvent { button, time, x, y });
+    }
+
+    Ok(events)
+}
@@ -1,6 +1,13 @@
+use crate::minesweeper::error::MinesweeperError;
 use crate::minesweeper::parsers::parser::Metadata;
 
-#[derive(Debug)]
+/// Width and height of the largest board, replays come from users and every field is allocated.
+pub const MAX_BOARD_SIZE: i32 = 100;
+
+/// A Minesweeper board, positions are `(x, y)` with `x` counting columns from the left
+/// and `y` rows from the top, both starting at 0.
+/// Fields are stored row by row, so `fields` and `changed_fields` are indexed with `[y][x]`.
+#[derive(Clone, Debug)]
 pub struct Board {
     pub fields: Vec<Vec<Field>>,
     pub changed_fields: Vec<Vec<bool>>,
@@ -26,41 +33,173 @@ pub enum FieldState {
 }
 
 impl Board {
-    pub(crate) fn open_field(&mut self, x: usize, y: usize) {
-        let field = &mut self.fields[y][x];
-
-        //If flagged or already open return
-        if field.field_state != FieldState::Closed {
-            return;
+    /// Creates a closed board with mines at the given `(x, y)` positions,
+    /// every board is created through this so all parsers use the same coordinates.
+    pub(crate) fn new(
+        metadata: &Metadata,
+        mines: &[(i32, i32)],
+    ) -> Result<Board, MinesweeperError> {
+        if metadata.x_size <= 0
+            || metadata.y_size <= 0
+            || metadata.x_size > MAX_BOARD_SIZE
+            || metadata.y_size > MAX_BOARD_SIZE
+        {
+            return Err(MinesweeperError::InvalidBoardSize);
         }
+        let total_fields = (metadata.x_size as u32)
+            .checked_mul(metadata.y_size as u32)
+            .ok_or(MinesweeperError::InvalidBoardSize)?;
 
-        field.field_state = FieldState::Open;
-        self.changed_fields[y][x] = true;
-        self.open_fields += 1;
+        let mut board = Board {
+            fields: vec![vec![Field::new(); metadata.x_size as usize]; metadata.y_size as usize],
+            changed_fields: vec![vec![true; metadata.x_size as usize]; metadata.y_size as usize],
+            metadata: metadata.clone(),
+            open_fields: 0,
+            mine_count: 0,
+            total_fields,
+        };
 
-        if field.mine {
-            return;
+        for &(x, y) in mines {
+            let field = board.field_mut(x, y)?;
+            if !field.mine {
+                field.mine = true;
+                board.mine_count += 1;
+            }
         }
 
-        if field.value == 0 {
+        for (x, y) in board.mine_locations() {
             for xd in -1..=1_i32 {
                 for yd in -1..=1_i32 {
-                    let xx = xd + x as i32;
-                    let yy = yd + y as i32;
-                    if xx < 0
-                        || xx >= self.metadata.x_size
-                        || yy < 0
-                        || yy >= self.metadata.y_size
-                        || xd == 0 && yd == 0
-                    {
+                    let xx = x + xd;
+                    let yy = y + yd;
+                    if xx < 0 || xx >= metadata.x_size || yy < 0 || yy >= metadata.y_size {
                         continue;
                     }
-                    self.open_field(xx as usize, yy as usize)
+
+                    let checked_field = &mut board.fields[yy as usize][xx as usize];
+                    if !checked_field.mine {
+                        checked_field.value += 1;
+                    }
+                }
+            }
+        }
+
+        Ok(board)
+    }
+
+    /// Whether `(x, y)` is a position on this board.
+    pub(crate) fn contains(&self, x: i32, y: i32) -> bool {
+        x >= 0 && x < self.metadata.x_size && y >= 0 && y < self.metadata.y_size
+    }
+
+    pub(crate) fn field(&self, x: i32, y: i32) -> Result<&Field, MinesweeperError> {
+        if !self.contains(x, y) {
+            return Err(MinesweeperError::InvalidPosition);
+        }
+        Ok(&self.fields[y as usize][x as usize])
+    }
+
+    pub(crate) fn field_mut(&mut self, x: i32, y: i32) -> Result<&mut Field, MinesweeperError> {
+        if !self.contains(x, y) {
+            return Err(MinesweeperError::InvalidPosition);
+        }
+        Ok(&mut self.fields[y as usize][x as usize])
+    }
+
+    /// Positions of all mines as `(x, y)`, row by row.
+    pub(crate) fn mine_locations(&self) -> Vec<(i32, i32)> {
+        let mut mines = Vec::new();
+        for (y, row) in self.fields.iter().enumerate() {
+            for (x, field) in row.iter().enumerate() {
+                if field.mine {
+                    mines.push((x as i32, y as i32));
+                }
+            }
+        }
+        mines
+    }
+
+    /// Opens a field and the area around it if it has no mines around, positions outside are ignored.
+    /// The area is opened with a worklist instead of recursion, so large openings cannot overflow the stack.
+    pub(crate) fn open_field(&mut self, x: usize, y: usize) {
+        let mut pending = vec![(x as i32, y as i32)];
+
+        while let Some((x, y)) = pending.pop() {
+            if !self.contains(x, y) {
+                continue;
+            }
+
+            let field = &mut self.fields[y as usize][x as usize];
+
+            //If flagged or already open skip it
+            if field.field_state != FieldState::Closed {
+                continue;
+            }
+
+            field.field_state = FieldState::Open;
+            self.changed_fields[y as usize][x as usize] = true;
+            self.open_fields += 1;
+
+            if field.mine || field.value != 0 {
+                continue;
+            }
+
+            for xd in -1..=1_i32 {
+                for yd in -1..=1_i32 {
+                    if xd != 0 || yd != 0 {
+                        pending.push((x + xd, y + yd));
+                    }
                 }
             }
         }
     }
 
+    /// Opens all closed neighbours of an open number if exactly that many neighbours are flagged.
+    /// Returns the neighbours which got opened by the chord itself.
+    pub(crate) fn chord(&mut self, x: usize, y: usize) -> Vec<(usize, usize)> {
+        if !self.contains(x as i32, y as i32) {
+            return Vec::new();
+        }
+
+        let field = &self.fields[y][x];
+        if field.field_state != FieldState::Open || field.mine || field.value == 0 {
+            return Vec::new();
+        }
+
+        let mut flags = 0;
+        let mut closed = Vec::new();
+        for xd in -1..=1_i32 {
+            for yd in -1..=1_i32 {
+                let xx = xd + x as i32;
+                let yy = yd + y as i32;
+                if xx < 0
+                    || xx >= self.metadata.x_size
+                    || yy < 0
+                    || yy >= self.metadata.y_size
+                    || xd == 0 && yd == 0
+                {
+                    continue;
+                }
+
+                match self.fields[yy as usize][xx as usize].field_state {
+                    FieldState::Flagged => flags += 1,
+                    FieldState::Closed => closed.push((xx as usize, yy as usize)),
+                    _ => {}
+                }
+            }
+        }
+
+        if flags != self.fields[y][x].value {
+            return Vec::new();
+        }
+
+        for &(xx, yy) in closed.iter() {
+            self.open_field(xx, yy);
+        }
+
+        closed
+    }
+
     pub(crate) fn calculate_done_percentage(&self) -> u32 {
         ((self.open_fields as f32 / (self.total_fields - self.mine_count) as f32) * 100_f32) as u32
     }
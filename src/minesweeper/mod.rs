@@ -1,9 +1,17 @@
-mod base36;
+pub(crate) mod base36;
 mod base62;
+pub mod duration;
 pub mod error;
+pub mod exporters;
+mod font;
 pub mod game_data;
+pub mod game_session;
+pub mod generator;
+pub mod importers;
 pub mod minesweeper_logic;
 pub mod parsers;
 pub(crate) mod provider;
 pub mod renderer;
+pub mod summary_card;
 mod textures;
+pub mod validator;
@@ -1,3 +1,46 @@
+use crate::minesweeper::error::MinesweeperError;
+use crate::minesweeper::parsers::parser::{Iparser, ParsedData};
+
 pub mod parser;
+#[cfg(test)]
+mod tests;
 pub mod v1;
 pub mod v2;
+pub mod v3;
+
+/// Parses a game in any supported version, the version is the part before the first `=`.
+pub fn parse_game(game_data: &str) -> Result<ParsedData, MinesweeperError> {
+    let option = game_data
+        .split_once('=')
+        .ok_or(MinesweeperError::ApiDataParse)?;
+
+    let possible_parsers: Vec<&dyn Iparser> = vec![
+        &v1::parser::ParserV1,
+        &v2::parser::ParserV2,
+        &v3::parser::ParserV3,
+    ];
+
+    let parser = possible_parsers
+        .iter()
+        .find(|p| p.supported_versions().contains(&option.0))
+        .ok_or(MinesweeperError::UnsupportedVersion)?;
+
+    let split: Vec<&str> = option.1.split('+').collect();
+
+    if split.len() < 4 {
+        return Err(MinesweeperError::ApiDataParse);
+    }
+
+    let metadata = parser.parse_meta_data(split[0].trim())?;
+
+    Ok(ParsedData {
+        game_board: parser.parse_mine_data(split[1].trim(), &metadata)?,
+        open_data: parser.parse_open_data(split[2].trim())?,
+        flag_data: parser.parse_flag_data(split[3].trim())?,
+        chord_data: match split.get(4) {
+            Some(data) => parser.parse_chord_data(data.trim())?,
+            None => Vec::new(),
+        },
+        metadata,
+    })
+}
@@ -1,29 +1,27 @@
+use crate::minesweeper::error::MinesweeperError;
 use crate::minesweeper::minesweeper_logic::{Board, FieldState};
-use serde::{Deserialize, Serialize};
 
 pub trait Iparser {
     fn supported_versions(&self) -> Vec<&str>;
-    fn parse_mine_data(&self, data: &str, metadata: &Metadata) -> Board;
-    fn parse_mine_locations(&self, data: &str) -> Vec<(i32, i32)>;
-    fn parse_flag_data(&self, data: &str) -> Vec<FlagAction>;
-    fn parse_open_data(&self, data: &str) -> Vec<OpenAction>;
-    fn parse_meta_data(&self, data: &str) -> Metadata;
-}
-
-#[derive(Serialize, Deserialize)]
-pub struct ApiData {
-    #[serde(rename = "gameData")]
-    pub game_data: String,
+    fn parse_mine_data(&self, data: &str, metadata: &Metadata) -> Result<Board, MinesweeperError>;
+    fn parse_mine_locations(&self, data: &str) -> Result<Vec<(i32, i32)>, MinesweeperError>;
+    fn parse_flag_data(&self, data: &str) -> Result<Vec<FlagAction>, MinesweeperError>;
+    fn parse_open_data(&self, data: &str) -> Result<Vec<OpenAction>, MinesweeperError>;
+    fn parse_meta_data(&self, data: &str) -> Result<Metadata, MinesweeperError>;
+    /// Chords were added with v3, older formats never contain them.
+    fn parse_chord_data(&self, _data: &str) -> Result<Vec<ChordAction>, MinesweeperError> {
+        Ok(Vec::new())
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Metadata {
     pub x_size: i32,
     pub y_size: i32,
     pub timeunits: i32,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct FlagAction {
     pub x: i32,
     pub y: i32,
@@ -32,14 +30,14 @@ pub struct FlagAction {
     pub total_time: i64,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub enum Action {
     Place,
     Remove,
     Toggle,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct OpenAction {
     pub x: i32,
     pub y: i32,
@@ -47,10 +45,20 @@ pub struct OpenAction {
     pub total_time: i64,
 }
 
+/// Opens all unflagged neighbours of an open number, if the number of flags around it matches.
+#[derive(Clone, Debug)]
+pub struct ChordAction {
+    pub x: i32,
+    pub y: i32,
+    pub time: i64,
+    pub total_time: i64,
+}
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub enum ActionType {
     Open,
     Flag,
+    Chord,
 }
 
 pub struct ParsedData {
@@ -58,10 +66,74 @@ pub struct ParsedData {
     pub game_board: Board,
     pub open_data: Vec<OpenAction>,
     pub flag_data: Vec<FlagAction>,
+    pub chord_data: Vec<ChordAction>,
+}
+
+/// An action of any kind, see [`ParsedData::timeline`].
+#[derive(Clone, Copy, Debug)]
+pub enum TimedAction<'a> {
+    Flag(&'a FlagAction),
+    Open(&'a OpenAction),
+    Chord(&'a ChordAction),
+}
+
+impl TimedAction<'_> {
+    pub fn position(&self) -> (i32, i32) {
+        match self {
+            TimedAction::Flag(action) => (action.x, action.y),
+            TimedAction::Open(action) => (action.x, action.y),
+            TimedAction::Chord(action) => (action.x, action.y),
+        }
+    }
+
+    pub fn total_time(&self) -> i64 {
+        match self {
+            TimedAction::Flag(action) => action.total_time,
+            TimedAction::Open(action) => action.total_time,
+            TimedAction::Chord(action) => action.total_time,
+        }
+    }
+
+    fn order(&self) -> u8 {
+        match self {
+            TimedAction::Flag(_) => 0,
+            TimedAction::Open(_) => 1,
+            TimedAction::Chord(_) => 2,
+        }
+    }
+}
+
+impl ParsedData {
+    /// All actions in the order they were performed, see [`timeline`].
+    pub fn timeline(&self) -> Vec<TimedAction<'_>> {
+        timeline(&self.flag_data, &self.open_data, &self.chord_data)
+    }
+}
+
+/// Actions of all kinds in the order they were performed,
+/// actions at the same time are performed flags first, then opens and chords like in the renderer.
+pub fn timeline<'a>(
+    flag_data: &'a [FlagAction],
+    open_data: &'a [OpenAction],
+    chord_data: &'a [ChordAction],
+) -> Vec<TimedAction<'a>> {
+    let mut actions: Vec<TimedAction> = flag_data
+        .iter()
+        .map(TimedAction::Flag)
+        .chain(open_data.iter().map(TimedAction::Open))
+        .chain(chord_data.iter().map(TimedAction::Chord))
+        .collect();
+    actions.sort_by_key(|action| (action.total_time(), action.order()));
+    actions
 }
 
 impl FlagAction {
+    /// Flags outside of the board are ignored.
     pub(crate) fn perform_action(&self, board: &mut Board) {
+        if !board.contains(self.x, self.y) {
+            return;
+        }
+
         match self.action {
             Action::Place => {
                 board.fields[self.y as usize][self.x as usize].field_state = FieldState::Flagged;
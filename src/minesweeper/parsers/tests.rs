@@ -0,0 +1,171 @@
+//! Non-square boards in versions 1 and 2, `x` is the column and `y` the row.
+//! Every test runs against the same board written in both versions.
+//! Malformed game data of every version has to be rejected without panicking.
+//! Renders have to perform the actions in the order they were played.
+
+use crate::minesweeper::error::MinesweeperError;
+use crate::minesweeper::minesweeper_logic::FieldState;
+use crate::minesweeper::parsers::parse_game;
+use crate::minesweeper::renderer::Renderer;
+
+/// 30x16 board with mines in three corners and the middle, opening `(0, 0)` clears the board.
+/// Version 1 writes mines as row and column, like the renderer of the original bot read them.
+const EXPERT: [&str; 2] = ["1=30x16+0TF0FT8F+005+T03P", "2=30x16,10+T00FTFF8+005+T03P"];
+
+/// Game data users could attach as a `.txt` replay, none of it is valid.
+const MALFORMED: [&str; 13] = [
+    "3=size:9x9;timeunits:10+!!+00+",
+    "3=size:9x9;timeunits:10+++00+!!",
+    "2=9x9,10+0+00+",
+    "2=9x9,10++0+",
+    "2=9x9,10++00!+",
+    "2=9x9,10+++00X",
+    "2=9x9,10+++0",
+    "2=9x9,10++0|zzzzzzzzzzzz:0+",
+    "2=9x9+++",
+    "2=axb,10+++",
+    "1=9x9++00abc+",
+    "1=9x9+++005T",
+    "1=9x9++0|0:9223372036854775807;0|0:1+",
+];
+
+/// A mine right of the board and one below it, for each version.
+const OUTSIDE: [[&str; 2]; 2] = [
+    ["1=30x16+0U+005+", "1=30x16+G0+005+"],
+    ["2=30x16,10+U0+005+", "2=30x16,10+0G+005+"],
+];
+
+#[test]
+fn parses_non_square_board() {
+    for game in EXPERT {
+        let data = parse_game(game).unwrap();
+
+        assert_eq!(data.metadata.x_size, 30, "{game}");
+        assert_eq!(data.metadata.y_size, 16, "{game}");
+        assert_eq!(data.game_board.fields.len(), 16, "{game}");
+        assert!(data.game_board.fields.iter().all(|row| row.len() == 30));
+        assert_eq!(data.game_board.total_fields, 480, "{game}");
+        assert_eq!(
+            data.game_board.mine_locations(),
+            vec![(29, 0), (15, 8), (0, 15), (29, 15)],
+            "{game}"
+        );
+    }
+}
+
+#[test]
+fn fields_are_accessed_by_column_and_row() {
+    for game in EXPERT {
+        let board = parse_game(game).unwrap().game_board;
+
+        assert!(board.field(29, 0).unwrap().mine, "{game}");
+        assert_eq!(board.field(28, 0).unwrap().value, 1, "{game}");
+        assert_eq!(board.field(14, 7).unwrap().value, 1, "{game}");
+
+        assert!(matches!(
+            board.field(30, 0),
+            Err(MinesweeperError::InvalidPosition)
+        ));
+        assert!(matches!(
+            board.field(0, 16),
+            Err(MinesweeperError::InvalidPosition)
+        ));
+        assert!(matches!(
+            board.field(-1, 0),
+            Err(MinesweeperError::InvalidPosition)
+        ));
+    }
+}
+
+#[test]
+fn actions_use_the_same_coordinates() {
+    for game in EXPERT {
+        let data = parse_game(game).unwrap();
+        let mut board = data.game_board;
+
+        let open = &data.open_data[0];
+        assert_eq!((open.x, open.y, open.total_time), (0, 0, 5), "{game}");
+        board.open_field(open.x as usize, open.y as usize);
+        assert_eq!(
+            board.open_fields,
+            board.total_fields - board.mine_count,
+            "{game}"
+        );
+
+        let flag = &data.flag_data[0];
+        assert_eq!((flag.x, flag.y), (29, 0), "{game}");
+        flag.perform_action(&mut board);
+        assert_eq!(
+            board.field(29, 0).unwrap().field_state,
+            FieldState::Flagged,
+            "{game}"
+        );
+    }
+}
+
+#[test]
+fn renders_non_square_board() {
+    for game in EXPERT {
+        for gif in [false, true] {
+            let data = parse_game(game).unwrap();
+            let mut renderer = Renderer::new(
+                data.metadata,
+                data.game_board,
+                data.open_data,
+                data.flag_data,
+                data.chord_data,
+                &gif,
+                &false,
+            );
+
+            let image = if gif {
+                renderer.render_gif()
+            } else {
+                renderer.render_jpeg()
+            };
+            assert!(image.is_ok_and(|image| !image.is_empty()), "{game}");
+        }
+    }
+}
+
+#[test]
+fn mines_outside_of_the_board_are_rejected() {
+    for game in OUTSIDE.into_iter().flatten() {
+        assert!(
+            matches!(parse_game(game), Err(MinesweeperError::InvalidPosition)),
+            "{game}"
+        );
+    }
+}
+
+#[test]
+fn malformed_game_data_is_rejected() {
+    for game in MALFORMED {
+        assert!(
+            matches!(parse_game(game), Err(MinesweeperError::ApiDataParse)),
+            "{game}"
+        );
+    }
+}
+
+#[test]
+fn jpeg_performs_actions_in_time_order() {
+    // The open at 2 hits the flag placed at 1, which is only removed at 3
+    let data = parse_game("2=4x4,10+00+332+331P;332R").unwrap();
+    let mut renderer = Renderer::new(
+        data.metadata,
+        data.game_board,
+        data.open_data,
+        data.flag_data,
+        data.chord_data,
+        &false,
+        &false,
+    );
+
+    assert!(renderer.render_jpeg().is_ok());
+    assert_eq!(renderer.game_board.open_fields, 0);
+    assert_eq!(
+        renderer.game_board.field(3, 3).unwrap().field_state,
+        FieldState::Closed
+    );
+}
@@ -1,5 +1,6 @@
 use crate::minesweeper::base62::decode;
-use crate::minesweeper::minesweeper_logic::{Board, Field};
+use crate::minesweeper::error::MinesweeperError;
+use crate::minesweeper::minesweeper_logic::Board;
 use std::str::FromStr;
 
 use crate::minesweeper::parsers::parser::{Action, FlagAction, Iparser, Metadata, OpenAction};
@@ -11,65 +12,15 @@ impl Iparser for ParserV2 {
         vec!["2"]
     }
 
-    fn parse_mine_data(&self, data: &str, metadata: &Metadata) -> Board {
-        let mines = self.parse_mine_locations(data);
-
-        let mut board = Board {
-            fields: vec![vec![Field::new(); metadata.y_size as usize]; metadata.x_size as usize],
-            changed_fields: vec![vec![true; metadata.y_size as usize]; metadata.x_size as usize],
-            metadata: metadata.clone(),
-            mine_count: mines.len() as u32,
-            open_fields: 0,
-            total_fields: (metadata.y_size * metadata.x_size) as u32,
-        };
-
-        for cords in mines {
-            let x = cords.0;
-            let y = cords.1;
-            let field = &mut board.fields[y as usize][x as usize];
-            field.mine = true;
-        }
-
-        for x in 0..metadata.x_size {
-            for y in 0..metadata.y_size {
-                let field = &mut board.fields[x as usize][y as usize];
-
-                if !field.mine {
-                    continue;
-                }
-
-                for xd in -1..=1_i32 {
-                    for zd in -1..=1_i32 {
-                        let xx = x + xd;
-                        let yy = y + zd;
-                        if xx < 0
-                            || xx >= metadata.x_size
-                            || yy < 0
-                            || yy >= metadata.y_size
-                            || (zd == 0 && xd == 0)
-                        {
-                            continue;
-                        }
-
-                        let checked_field = &mut board.fields[xx as usize][yy as usize];
-                        if checked_field.mine {
-                            continue;
-                        }
-
-                        checked_field.value += 1;
-                    }
-                }
-            }
-        }
-
-        board
+    fn parse_mine_data(&self, data: &str, metadata: &Metadata) -> Result<Board, MinesweeperError> {
+        Board::new(metadata, &self.parse_mine_locations(data)?)
     }
 
-    fn parse_mine_locations(&self, data: &str) -> Vec<(i32, i32)> {
+    fn parse_mine_locations(&self, data: &str) -> Result<Vec<(i32, i32)>, MinesweeperError> {
         let mut return_data = Vec::new();
 
         if data.chars().count() == 0 {
-            return return_data;
+            return Ok(return_data);
         }
 
         let raw_open_fields_data: Vec<&str> = data.split(';').collect();
@@ -78,45 +29,27 @@ impl Iparser for ParserV2 {
             if raw_open_field.contains('|') {
                 let part = raw_open_field
                     .split_once('|')
-                    .expect("Unable to parse mine locations");
+                    .ok_or(MinesweeperError::ApiDataParse)?;
 
-                return_data.push((decode(part.0) as i32, decode(part.1) as i32));
+                return_data.push((decode(part.0)? as i32, decode(part.1)? as i32));
             } else {
-                raw_open_field
-                    .chars()
-                    .collect::<Vec<char>>()
-                    .chunks(2)
-                    .map(|chunk| chunk.iter().collect::<String>())
-                    .for_each(|x| {
-                        let mut chars = x.chars();
-                        return_data.push((
-                            decode(
-                                chars
-                                    .next()
-                                    .expect("Unable to parse mine locations")
-                                    .to_string()
-                                    .as_str(),
-                            ) as i32,
-                            decode(
-                                chars
-                                    .next()
-                                    .expect("Unable to parse mine locations")
-                                    .to_string()
-                                    .as_str(),
-                            ) as i32,
-                        ))
-                    });
+                let raw_chars: Vec<char> = raw_open_field.chars().collect();
+                for chunk in raw_chars.chunks(2) {
+                    let mut chars = chunk.iter();
+                    return_data.push((decode_char(chars.next())?, decode_char(chars.next())?));
+                }
             }
         }
 
-        return_data
+        Ok(return_data)
     }
 
-    fn parse_flag_data(&self, data: &str) -> Vec<FlagAction> {
+    fn parse_flag_data(&self, data: &str) -> Result<Vec<FlagAction>, MinesweeperError> {
         let mut return_data = Vec::new();
+        let mut total_time: i64 = 0;
 
         if data.chars().count() == 0 {
-            return return_data;
+            return Ok(return_data);
         }
 
         let raw_open_fields_data: Vec<&str> = data.split(';').collect();
@@ -125,63 +58,55 @@ impl Iparser for ParserV2 {
             if raw_open_field.contains('|') {
                 let mut chars = raw_open_field.chars();
 
-                let action_type = chars.next_back().expect("Unable to parse flag data");
+                let action_type = chars.next_back().ok_or(MinesweeperError::ApiDataParse)?;
                 let part_one = chars
                     .as_str()
                     .split_once('|')
-                    .expect("Unable to parse flag data");
+                    .ok_or(MinesweeperError::ApiDataParse)?;
                 let part_two = part_one
                     .1
                     .split_once(':')
-                    .expect("Unable to parse flag data");
+                    .ok_or(MinesweeperError::ApiDataParse)?;
 
-                let time = decode(part_two.1);
+                let time = decode(part_two.1)?;
+                total_time = add_time(total_time, time)?;
 
                 return_data.push(FlagAction {
-                    x: decode(part_one.0) as i32,
-                    y: decode(part_two.0) as i32,
+                    x: decode(part_one.0)? as i32,
+                    y: decode(part_two.0)? as i32,
                     time,
-                    action: get_flag_type(action_type),
-                    total_time: time + return_data.iter().map(|x| x.time).sum::<i64>(),
+                    action: get_flag_type(action_type)?,
+                    total_time,
                 });
             } else {
                 let mut chars = raw_open_field.chars();
 
-                let x = decode(
-                    chars
-                        .next()
-                        .expect("Unable to parse flag data")
-                        .to_string()
-                        .as_str(),
-                ) as i32;
-                let y = decode(
-                    chars
-                        .next()
-                        .expect("Unable to parse flag data")
-                        .to_string()
-                        .as_str(),
-                ) as i32;
-                let action = get_flag_type(chars.next_back().expect("Unable to parse flag data"));
-                let time = decode(chars.as_str());
+                let x = decode_char(chars.next().as_ref())?;
+                let y = decode_char(chars.next().as_ref())?;
+                let action =
+                    get_flag_type(chars.next_back().ok_or(MinesweeperError::ApiDataParse)?)?;
+                let time = decode(chars.as_str())?;
+                total_time = add_time(total_time, time)?;
 
                 return_data.push(FlagAction {
                     x,
                     y,
                     action,
                     time,
-                    total_time: time + return_data.iter().map(|x| x.time).sum::<i64>(),
+                    total_time,
                 });
             }
         }
 
-        return_data
+        Ok(return_data)
     }
 
-    fn parse_open_data(&self, data: &str) -> Vec<OpenAction> {
+    fn parse_open_data(&self, data: &str) -> Result<Vec<OpenAction>, MinesweeperError> {
         let mut return_data = Vec::new();
+        let mut total_time: i64 = 0;
 
         if data.chars().count() == 0 {
-            return return_data;
+            return Ok(return_data);
         }
 
         let raw_open_fields_data: Vec<&str> = data.split(';').collect();
@@ -190,70 +115,72 @@ impl Iparser for ParserV2 {
             if raw_open_field.contains('|') {
                 let part_one = raw_open_field
                     .split_once('|')
-                    .expect("Unable to parse open data");
+                    .ok_or(MinesweeperError::ApiDataParse)?;
                 let part_two = part_one
                     .1
                     .split_once(':')
-                    .expect("Unable to parse open data");
+                    .ok_or(MinesweeperError::ApiDataParse)?;
 
-                let time = decode(part_two.1);
+                let time = decode(part_two.1)?;
+                total_time = add_time(total_time, time)?;
 
                 return_data.push(OpenAction {
-                    x: decode(part_one.0) as i32,
-                    y: decode(part_two.0) as i32,
+                    x: decode(part_one.0)? as i32,
+                    y: decode(part_two.0)? as i32,
                     time,
-                    total_time: time + return_data.iter().map(|x| x.time).sum::<i64>(),
+                    total_time,
                 });
             } else {
                 let mut chars = raw_open_field.chars();
 
-                let x = decode(
-                    chars
-                        .next()
-                        .expect("Unable to parse open data")
-                        .to_string()
-                        .as_str(),
-                ) as i32;
-                let y = decode(
-                    chars
-                        .next()
-                        .expect("Unable to parse open data")
-                        .to_string()
-                        .as_str(),
-                ) as i32;
-                let time = decode(chars.as_str());
+                let x = decode_char(chars.next().as_ref())?;
+                let y = decode_char(chars.next().as_ref())?;
+                let time = decode(chars.as_str())?;
+                total_time = add_time(total_time, time)?;
 
                 return_data.push(OpenAction {
                     x,
                     y,
                     time,
-                    total_time: time + return_data.iter().map(|x| x.time).sum::<i64>(),
+                    total_time,
                 });
             }
         }
 
-        return_data
+        Ok(return_data)
     }
 
-    fn parse_meta_data(&self, data: &str) -> Metadata {
-        let data_split_1 = data.split_once('x').expect("Unable to parse Metadata");
+    fn parse_meta_data(&self, data: &str) -> Result<Metadata, MinesweeperError> {
+        let data_split_1 = data.split_once('x').ok_or(MinesweeperError::ApiDataParse)?;
         let data_split_2 = data_split_1
             .1
             .split_once(',')
-            .expect("Unable to parse Metadata");
-        Metadata {
-            x_size: i32::from_str(data_split_1.0).expect("Unable to parse Metadata"),
-            y_size: i32::from_str(data_split_2.0).expect("Unable to parse Metadata"),
-            timeunits: i32::from_str(data_split_2.1).expect("Unable to parse Metadata"),
-        }
+            .ok_or(MinesweeperError::ApiDataParse)?;
+        Ok(Metadata {
+            x_size: i32::from_str(data_split_1.0).map_err(|_| MinesweeperError::ApiDataParse)?,
+            y_size: i32::from_str(data_split_2.0).map_err(|_| MinesweeperError::ApiDataParse)?,
+            timeunits: i32::from_str(data_split_2.1).map_err(|_| MinesweeperError::ApiDataParse)?,
+        })
     }
 }
 
-fn get_flag_type(raw_flag_type: char) -> Action {
+/// Positions without a separator are written with one base62 character per coordinate.
+fn decode_char(character: Option<&char>) -> Result<i32, MinesweeperError> {
+    let character = character.ok_or(MinesweeperError::ApiDataParse)?;
+    Ok(decode(character.to_string().as_str())? as i32)
+}
+
+fn add_time(total_time: i64, time: i64) -> Result<i64, MinesweeperError> {
+    total_time
+        .checked_add(time)
+        .ok_or(MinesweeperError::ApiDataParse)
+}
+
+fn get_flag_type(raw_flag_type: char) -> Result<Action, MinesweeperError> {
     match raw_flag_type {
-        'P' => Action::Place,
-        'R' => Action::Remove,
-        'T' => Action::Toggle,
-        _ => unreachable!(),
+        'P' => Ok(Action::Place),
+        'R' => Ok(Action::Remove),
+        'T' => Ok(Action::Toggle),
+        _ => Err(MinesweeperError::ApiDataParse),
     }
 }
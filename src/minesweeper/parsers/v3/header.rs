@@ -0,0 +1,140 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::minesweeper::error::MinesweeperError;
+use crate::minesweeper::game_session::GameState;
+use crate::minesweeper::generator::generator::GeneratorType;
+use crate::minesweeper::minesweeper_logic::MAX_BOARD_SIZE;
+use crate::minesweeper::parsers::parser::Metadata;
+
+/// The key/value header of a v3 game, see `docs/replay-format-v3.md`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Header {
+    pub metadata: Metadata,
+    pub mine_count: Option<u32>,
+    pub generator: Option<GeneratorType>,
+    pub seed: Option<u64>,
+    /// Zero based `(x, y)` of the first open
+    pub first_click: Option<(i32, i32)>,
+    pub result: Option<GameState>,
+    /// Whether chords were recorded, games without chord support leave this off
+    pub chords: bool,
+}
+
+impl Header {
+    pub fn parse(data: &str) -> Result<Header, MinesweeperError> {
+        let mut size = None;
+        let mut timeunits = None;
+        let mut header = Header {
+            metadata: Metadata {
+                x_size: 0,
+                y_size: 0,
+                timeunits: 0,
+            },
+            mine_count: None,
+            generator: None,
+            seed: None,
+            first_click: None,
+            result: None,
+            chords: false,
+        };
+
+        for entry in data.split(';').filter(|entry| !entry.is_empty()) {
+            let (key, value) = entry
+                .split_once(':')
+                .ok_or(MinesweeperError::InvalidHeader)?;
+
+            match key {
+                "size" => size = Some(parse_pair(value, 'x')?),
+                "timeunits" => timeunits = Some(parse_number::<i32>(value)?),
+                "mines" => header.mine_count = Some(parse_number(value)?),
+                "generator" => {
+                    header.generator = Some(
+                        GeneratorType::from_str(value)
+                            .map_err(|_| MinesweeperError::InvalidHeader)?,
+                    )
+                }
+                "seed" => header.seed = Some(parse_number(value)?),
+                "first_click" => header.first_click = Some(parse_pair(value, ',')?),
+                "result" => {
+                    header.result = Some(match value {
+                        "won" => GameState::Won,
+                        "lost" => GameState::Lost,
+                        "running" => GameState::Running,
+                        _ => return Err(MinesweeperError::InvalidHeader),
+                    })
+                }
+                "chords" => {
+                    header.chords = match value {
+                        "0" => false,
+                        "1" => true,
+                        _ => return Err(MinesweeperError::InvalidHeader),
+                    }
+                }
+                // Unknown keys are skipped, so newer games can still be rendered
+                _ => {}
+            }
+        }
+
+        let (x_size, y_size) = size.ok_or(MinesweeperError::InvalidHeader)?;
+        let timeunits = timeunits.ok_or(MinesweeperError::InvalidHeader)?;
+        if x_size <= 0 || y_size <= 0 || timeunits <= 0 {
+            return Err(MinesweeperError::InvalidHeader);
+        }
+        if x_size > MAX_BOARD_SIZE || y_size > MAX_BOARD_SIZE {
+            return Err(MinesweeperError::InvalidBoardSize);
+        }
+
+        header.metadata = Metadata {
+            x_size,
+            y_size,
+            timeunits,
+        };
+
+        Ok(header)
+    }
+}
+
+impl Display for Header {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "size:{}x{};timeunits:{}",
+            self.metadata.x_size, self.metadata.y_size, self.metadata.timeunits
+        )?;
+
+        if let Some(mine_count) = self.mine_count {
+            write!(f, ";mines:{mine_count}")?;
+        }
+        if let Some(generator) = self.generator {
+            write!(f, ";generator:{}", generator.id())?;
+        }
+        if let Some(seed) = self.seed {
+            write!(f, ";seed:{seed}")?;
+        }
+        if let Some((x, y)) = self.first_click {
+            write!(f, ";first_click:{x},{y}")?;
+        }
+        if let Some(result) = self.result {
+            let result = match result {
+                GameState::Won => "won",
+                GameState::Lost => "lost",
+                GameState::Running => "running",
+            };
+            write!(f, ";result:{result}")?;
+        }
+
+        write!(f, ";chords:{}", if self.chords { 1 } else { 0 })
+    }
+}
+
+fn parse_number<T: FromStr>(value: &str) -> Result<T, MinesweeperError> {
+    value.parse().map_err(|_| MinesweeperError::InvalidHeader)
+}
+
+fn parse_pair(value: &str, separator: char) -> Result<(i32, i32), MinesweeperError> {
+    let (first, second) = value
+        .split_once(separator)
+        .ok_or(MinesweeperError::InvalidHeader)?;
+    Ok((parse_number(first)?, parse_number(second)?))
+}
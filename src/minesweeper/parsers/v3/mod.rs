@@ -0,0 +1,5 @@
+pub mod header;
+pub mod parser;
+#[cfg(test)]
+mod tests;
+pub mod writer;
@@ -0,0 +1,48 @@
+use crate::minesweeper::error::MinesweeperError;
+use crate::minesweeper::minesweeper_logic::Board;
+use crate::minesweeper::parsers::parser::{ChordAction, FlagAction, Iparser, Metadata, OpenAction};
+use crate::minesweeper::parsers::v2::parser::ParserV2;
+use crate::minesweeper::parsers::v3::header::Header;
+
+/// v3 replaces the metadata of v2 with a key/value header and adds a part for chords,
+/// positions are encoded the same way as in v2.
+pub struct ParserV3;
+
+impl Iparser for ParserV3 {
+    fn supported_versions(&self) -> Vec<&str> {
+        vec!["3"]
+    }
+
+    fn parse_mine_data(&self, data: &str, metadata: &Metadata) -> Result<Board, MinesweeperError> {
+        ParserV2.parse_mine_data(data, metadata)
+    }
+
+    fn parse_mine_locations(&self, data: &str) -> Result<Vec<(i32, i32)>, MinesweeperError> {
+        ParserV2.parse_mine_locations(data)
+    }
+
+    fn parse_flag_data(&self, data: &str) -> Result<Vec<FlagAction>, MinesweeperError> {
+        ParserV2.parse_flag_data(data)
+    }
+
+    fn parse_open_data(&self, data: &str) -> Result<Vec<OpenAction>, MinesweeperError> {
+        ParserV2.parse_open_data(data)
+    }
+
+    fn parse_meta_data(&self, data: &str) -> Result<Metadata, MinesweeperError> {
+        Header::parse(data).map(|header| header.metadata)
+    }
+
+    fn parse_chord_data(&self, data: &str) -> Result<Vec<ChordAction>, MinesweeperError> {
+        Ok(ParserV2
+            .parse_open_data(data)?
+            .into_iter()
+            .map(|action| ChordAction {
+                x: action.x,
+                y: action.y,
+                time: action.time,
+                total_time: action.total_time,
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,205 @@
+//! Conformance tests for the examples in `docs/replay-format-v3.md`.
+
+use crate::minesweeper::error::MinesweeperError;
+use crate::minesweeper::game_session::GameState;
+use crate::minesweeper::generator::generator::GeneratorType;
+use crate::minesweeper::parsers::parse_game;
+use crate::minesweeper::parsers::parser::{Action, Metadata};
+use crate::minesweeper::parsers::v3::header::Header;
+use crate::minesweeper::parsers::v3::writer;
+
+const EXAMPLE: &str = "3=size:4x4;timeunits:10;mines:2;generator:random;seed:42;first_click:3,0;result:won;chords:1+00;12+302+005P;123P+11A;022";
+
+fn example_header() -> Header {
+    Header {
+        metadata: Metadata {
+            x_size: 4,
+            y_size: 4,
+            timeunits: 10,
+        },
+        mine_count: Some(2),
+        generator: Some(GeneratorType::Random),
+        seed: Some(42),
+        first_click: Some((3, 0)),
+        result: Some(GameState::Won),
+        chords: true,
+    }
+}
+
+#[test]
+fn parses_example() {
+    let data = parse_game(EXAMPLE).unwrap();
+
+    assert_eq!(data.metadata, example_header().metadata);
+    assert_eq!(data.game_board.mine_locations(), vec![(0, 0), (1, 2)]);
+
+    let opens: Vec<(i32, i32, i64)> = data
+        .open_data
+        .iter()
+        .map(|x| (x.x, x.y, x.total_time))
+        .collect();
+    assert_eq!(opens, vec![(3, 0, 2)]);
+
+    let flags: Vec<(i32, i32, i64)> = data
+        .flag_data
+        .iter()
+        .map(|x| (x.x, x.y, x.total_time))
+        .collect();
+    assert_eq!(flags, vec![(0, 0, 5), (1, 2, 8)]);
+    assert!(
+        data.flag_data
+            .iter()
+            .all(|x| matches!(x.action, Action::Place))
+    );
+
+    let chords: Vec<(i32, i32, i64)> = data
+        .chord_data
+        .iter()
+        .map(|x| (x.x, x.y, x.total_time))
+        .collect();
+    assert_eq!(chords, vec![(1, 1, 10), (0, 2, 12)]);
+}
+
+#[test]
+fn example_is_won_after_all_actions() {
+    let data = parse_game(EXAMPLE).unwrap();
+    let mut board = data.game_board;
+
+    board.open_field(3, 0);
+    data.flag_data
+        .iter()
+        .for_each(|action| action.perform_action(&mut board));
+    data.chord_data.iter().for_each(|action| {
+        board.chord(action.x as usize, action.y as usize);
+    });
+
+    assert_eq!(board.open_fields, board.total_fields - board.mine_count);
+}
+
+#[test]
+fn writer_produces_example() {
+    let data = parse_game(EXAMPLE).unwrap();
+
+    let written = writer::write(
+        &example_header(),
+        &data.game_board.mine_locations(),
+        &data.open_data,
+        &data.flag_data,
+        &data.chord_data,
+    );
+
+    assert_eq!(written, EXAMPLE);
+}
+
+#[test]
+fn header_round_trip() {
+    let header = example_header();
+    assert_eq!(Header::parse(header.to_string().as_str()).unwrap(), header);
+
+    let minimal = Header {
+        mine_count: None,
+        generator: None,
+        seed: None,
+        first_click: None,
+        result: None,
+        chords: false,
+        ..example_header()
+    };
+    assert_eq!(minimal.to_string(), "size:4x4;timeunits:10;chords:0");
+    assert_eq!(
+        Header::parse(minimal.to_string().as_str()).unwrap(),
+        minimal
+    );
+}
+
+#[test]
+fn header_keys_in_any_order() {
+    let header = Header::parse("timeunits:10;chords:1;size:4x4").unwrap();
+    assert_eq!(header.metadata.x_size, 4);
+    assert_eq!(header.metadata.timeunits, 10);
+    assert!(header.chords);
+}
+
+#[test]
+fn unknown_header_keys_are_ignored() {
+    let header = Header::parse("size:4x4;timeunits:10;replay_author:someone").unwrap();
+    assert_eq!(header.metadata.y_size, 4);
+
+    let data = parse_game("3=size:4x4;timeunits:10;replay_author:someone+00;12+302+").unwrap();
+    assert_eq!(data.open_data.len(), 1);
+}
+
+#[test]
+fn invalid_headers_are_rejected() {
+    for header in [
+        "timeunits:10",
+        "size:4x4",
+        "size:4;timeunits:10",
+        "size:0x4;timeunits:10",
+        "size:4x4;timeunits:0",
+        "size:4x4;timeunits:10;result:draw",
+        "size:4x4;timeunits:10;chords:yes",
+        "size:4x4;timeunits:10;generator:unknown",
+        "size:4x4;timeunits:10;first_click:3",
+        "size:4x4;timeunits",
+    ] {
+        assert!(
+            matches!(Header::parse(header), Err(MinesweeperError::InvalidHeader)),
+            "{header} should be rejected"
+        );
+    }
+
+    assert!(matches!(
+        parse_game("3=size:4x4+00;12+302+"),
+        Err(MinesweeperError::InvalidHeader)
+    ));
+}
+
+#[test]
+fn oversized_boards_are_rejected() {
+    assert!(matches!(
+        Header::parse("size:40000x40000;timeunits:1"),
+        Err(MinesweeperError::InvalidBoardSize)
+    ));
+    assert!(matches!(
+        parse_game("3=size:40000x40000;timeunits:1+++"),
+        Err(MinesweeperError::InvalidBoardSize)
+    ));
+    assert!(matches!(
+        parse_game("2=40000x40000,1+++"),
+        Err(MinesweeperError::InvalidBoardSize)
+    ));
+}
+
+#[test]
+fn largest_opening_does_not_overflow_the_stack() {
+    let data = parse_game("3=size:100x100;timeunits:1++000+").unwrap();
+    let mut board = data.game_board;
+
+    board.open_field(0, 0);
+    assert_eq!(board.open_fields, 100 * 100);
+}
+
+#[test]
+fn chord_part_is_optional() {
+    let data = parse_game("3=size:4x4;timeunits:10+00;12+302+005P").unwrap();
+    assert!(data.chord_data.is_empty());
+    assert_eq!(data.flag_data.len(), 1);
+}
+
+#[test]
+fn older_versions_are_still_dispatched() {
+    let data = parse_game("2=4x4,10+00;12+302+005P").unwrap();
+    assert_eq!(data.metadata, example_header().metadata);
+    assert_eq!(data.open_data.len(), 1);
+    assert!(data.chord_data.is_empty());
+
+    assert!(matches!(
+        parse_game("9=4x4,10+00+302+"),
+        Err(MinesweeperError::UnsupportedVersion)
+    ));
+    assert!(matches!(
+        parse_game("3=size:4x4;timeunits:10+00"),
+        Err(MinesweeperError::ApiDataParse)
+    ));
+}
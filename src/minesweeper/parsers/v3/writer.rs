@@ -0,0 +1,66 @@
+use crate::minesweeper::base62::encode;
+use crate::minesweeper::parsers::parser::{Action, ChordAction, FlagAction, OpenAction};
+use crate::minesweeper::parsers::v3::header::Header;
+
+/// Writes a game in the format read by `ParserV3`.
+pub fn write(
+    header: &Header,
+    mines: &[(i32, i32)],
+    open_data: &[OpenAction],
+    flag_data: &[FlagAction],
+    chord_data: &[ChordAction],
+) -> String {
+    let mines = mines
+        .iter()
+        .map(|&(x, y)| position(x, y))
+        .collect::<Vec<String>>()
+        .join(";");
+
+    let open_data = open_data
+        .iter()
+        .map(|action| timed_position(action.x, action.y, action.time))
+        .collect::<Vec<String>>()
+        .join(";");
+
+    let flag_data = flag_data
+        .iter()
+        .map(|action| {
+            timed_position(action.x, action.y, action.time)
+                + match action.action {
+                    Action::Place => "P",
+                    Action::Remove => "R",
+                    Action::Toggle => "T",
+                }
+        })
+        .collect::<Vec<String>>()
+        .join(";");
+
+    let chord_data = chord_data
+        .iter()
+        .map(|action| timed_position(action.x, action.y, action.time))
+        .collect::<Vec<String>>()
+        .join(";");
+
+    format!("3={header}+{mines}+{open_data}+{flag_data}+{chord_data}")
+}
+
+/// Coordinates up to 61 fit into a single character, larger ones need the separated form.
+fn position(x: i32, y: i32) -> String {
+    if is_short(x) && is_short(y) {
+        encode(x as i64) + encode(y as i64).as_str()
+    } else {
+        format!("{}|{}", encode(x as i64), encode(y as i64))
+    }
+}
+
+fn timed_position(x: i32, y: i32, time: i64) -> String {
+    if is_short(x) && is_short(y) {
+        position(x, y) + encode(time).as_str()
+    } else {
+        format!("{}:{}", position(x, y), encode(time))
+    }
+}
+
+fn is_short(coordinate: i32) -> bool {
+    (0..62).contains(&coordinate)
+}
@@ -1,35 +1,69 @@
 use crate::minesweeper::error::MinesweeperError;
-use crate::minesweeper::provider::provider::{ApiData, PlayerData, Provider};
+use crate::minesweeper::provider::http::HttpClient;
+use crate::minesweeper::provider::provider::{
+    ApiData, PlayerData, Provider, find_after, without_scheme,
+};
 
-pub struct GreevProvider;
+const DEFAULT_BASE_URL: &str = "http://api.greev.eu/v2";
+
+pub struct GreevProvider {
+    id: String,
+    name: String,
+    base_url: String,
+    http: HttpClient,
+}
+
+impl GreevProvider {
+    pub fn new(id: String, name: String, base_url: Option<String>) -> GreevProvider {
+        GreevProvider {
+            id,
+            name,
+            base_url: base_url.unwrap_or(DEFAULT_BASE_URL.to_string()),
+            http: HttpClient::new(),
+        }
+    }
+}
 
 impl Provider for GreevProvider {
     fn id(&self) -> &str {
-        "greev"
+        self.id.as_str()
     }
 
     fn name(&self) -> &str {
-        "Greev"
+        self.name.as_str()
     }
 
     fn fetch_data(&self, gameid: &str) -> Result<ApiData, MinesweeperError> {
-        let request_data =
-            ureq::get(format!("http://api.greev.eu/v2/stats/minesweeper/game/{gameid}").as_ref())
-                .call()
-                .map_err(|_| MinesweeperError::GameDataNotFound)?
-                .into_string()
-                .map_err(|_| MinesweeperError::GameDataNotFound)?;
+        let request_data = self.http.get(
+            format!("{}/stats/minesweeper/game/{gameid}", self.base_url).as_str(),
+            &[],
+        )?;
 
         serde_json::from_str(request_data.as_ref()).map_err(|_| MinesweeperError::ApiDataParse)
     }
 
     fn fetch_name(&self, uuid: &str) -> Result<PlayerData, MinesweeperError> {
-        let request_data = ureq::get(format!("http://api.greev.eu/v2/player/name/{uuid}").as_ref())
-            .call()
-            .map_err(|_| MinesweeperError::GameDataNotFound)?
-            .into_string()
-            .map_err(|_| MinesweeperError::GameDataNotFound)?;
+        let request_data = self.http.get(
+            format!("{}/player/name/{uuid}", self.base_url).as_str(),
+            &[],
+        )?;
 
         serde_json::from_str(request_data.as_ref()).map_err(|_| MinesweeperError::ApiDataParse)
     }
+
+    fn find_game_ids(&self, text: &str) -> Vec<String> {
+        let mut game_ids: Vec<String> = find_after(text, format!("{}:", self.id).as_str())
+            .into_iter()
+            .filter(|game_id| self.is_valid_game_id(game_id))
+            .collect();
+        game_ids.extend(
+            find_after(
+                text,
+                format!("{}/stats/minesweeper/game/", without_scheme(&self.base_url)).as_str(),
+            )
+            .into_iter()
+            .filter(|game_id| self.is_valid_game_id(game_id)),
+        );
+        game_ids
+    }
 }
@@ -0,0 +1,169 @@
+use std::io::Read;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::minesweeper::error::MinesweeperError;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+/// Larger downloads, e.g. images, are cut off
+const MAX_BODY_BYTES: u64 = 10 * 1024 * 1024;
+/// Retries after the first attempt, only for server and network errors
+const MAX_RETRIES: u32 = 2;
+/// Doubled after every retry
+const INITIAL_BACKOFF: Duration = Duration::from_millis(250);
+/// Failed requests in a row after which the API is treated as down
+const BREAKER_THRESHOLD: u32 = 5;
+/// How long no requests are sent to an API which is down
+const BREAKER_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// Sends the requests of one provider, so an API which is down does not affect the others.
+pub struct HttpClient {
+    agent: ureq::Agent,
+    breaker: Mutex<CircuitBreaker>,
+}
+
+#[derive(Default)]
+struct CircuitBreaker {
+    failures: u32,
+    open_until: Option<Instant>,
+    /// A request is testing the API after the cooldown, every other one is refused meanwhile
+    probing: bool,
+}
+
+impl Default for HttpClient {
+    fn default() -> Self {
+        HttpClient::new()
+    }
+}
+
+enum Failure {
+    /// Worth another attempt, e.g. a timeout or an internal server error
+    Retry,
+    Final(MinesweeperError),
+}
+
+impl HttpClient {
+    pub fn new() -> HttpClient {
+        HttpClient {
+            agent: ureq::AgentBuilder::new()
+                .timeout_connect(CONNECT_TIMEOUT)
+                .timeout_read(READ_TIMEOUT)
+                .build(),
+            breaker: Mutex::new(CircuitBreaker::default()),
+        }
+    }
+
+    /// Returns the body of the response, server and network errors are retried with backoff.
+    pub fn get(&self, url: &str, headers: &[(&str, &str)]) -> Result<String, MinesweeperError> {
+        self.request(url, headers, |response| response.into_string())
+    }
+
+    /// Like [`HttpClient::get`] for binary responses like images.
+    pub fn get_bytes(
+        &self,
+        url: &str,
+        headers: &[(&str, &str)],
+    ) -> Result<Vec<u8>, MinesweeperError> {
+        self.request(url, headers, |response| {
+            let mut body = Vec::new();
+            response
+                .into_reader()
+                .take(MAX_BODY_BYTES)
+                .read_to_end(&mut body)?;
+            Ok(body)
+        })
+    }
+
+    fn request<T>(
+        &self,
+        url: &str,
+        headers: &[(&str, &str)],
+        read: impl Fn(ureq::Response) -> std::io::Result<T>,
+    ) -> Result<T, MinesweeperError> {
+        if !self.allow_request() {
+            return Err(MinesweeperError::UpstreamDown);
+        }
+
+        let mut backoff = INITIAL_BACKOFF;
+        for attempt in 0..=MAX_RETRIES {
+            if attempt > 0 {
+                thread::sleep(backoff);
+                backoff *= 2;
+            }
+
+            match self.attempt(url, headers, &read) {
+                Ok(body) => {
+                    self.record(true);
+                    return Ok(body);
+                }
+                Err(Failure::Final(error)) => {
+                    self.record(true);
+                    return Err(error);
+                }
+                Err(Failure::Retry) => {}
+            }
+        }
+
+        self.record(false);
+        Err(MinesweeperError::UpstreamDown)
+    }
+
+    fn attempt<T>(
+        &self,
+        url: &str,
+        headers: &[(&str, &str)],
+        read: impl Fn(ureq::Response) -> std::io::Result<T>,
+    ) -> Result<T, Failure> {
+        let mut request = self.agent.get(url);
+        for (header, value) in headers {
+            request = request.set(header, value);
+        }
+
+        match request.call() {
+            Ok(response) => read(response).map_err(|_| Failure::Retry),
+            Err(ureq::Error::Status(status, _)) => Err(match status {
+                401 | 403 => Failure::Final(MinesweeperError::Unauthorized),
+                429 => Failure::Final(MinesweeperError::RateLimited),
+                500..=599 => Failure::Retry,
+                _ => Failure::Final(MinesweeperError::GameDataNotFound),
+            }),
+            Err(ureq::Error::Transport(_)) => Err(Failure::Retry),
+        }
+    }
+
+    /// Whether requests are refused, during the cooldown and while a single request probes the API.
+    pub fn is_open(&self) -> bool {
+        let breaker = self.breaker.lock().expect("Circuit breaker was poisoned");
+        breaker.probing
+            || breaker
+                .open_until
+                .is_some_and(|open_until| Instant::now() < open_until)
+    }
+
+    /// After the cooldown one request is let through, the API is down again if it fails.
+    fn allow_request(&self) -> bool {
+        let mut breaker = self.breaker.lock().expect("Circuit breaker was poisoned");
+        match breaker.open_until {
+            None => true,
+            Some(open_until) if Instant::now() < open_until => false,
+            Some(_) => !std::mem::replace(&mut breaker.probing, true),
+        }
+    }
+
+    /// Every answer of the API counts as success, even an error like 404.
+    fn record(&self, success: bool) {
+        let mut breaker = self.breaker.lock().expect("Circuit breaker was poisoned");
+        if success {
+            *breaker = CircuitBreaker::default();
+            return;
+        }
+
+        breaker.failures += 1;
+        breaker.probing = false;
+        if breaker.failures >= BREAKER_THRESHOLD {
+            breaker.open_until = Some(Instant::now() + BREAKER_COOLDOWN);
+        }
+    }
+}
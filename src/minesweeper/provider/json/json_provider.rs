@@ -0,0 +1,247 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::minesweeper::error::MinesweeperError;
+use crate::minesweeper::provider::http::HttpClient;
+use crate::minesweeper::provider::provider::{ApiData, PlayerData, Provider};
+
+/// How to read games from an API which returns JSON, declared in the provider config.
+/// URLs are templates, `{base_url}`, `{game_id}`, `{uuid}` and `{api_key}` are replaced.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JsonConfig {
+    pub game_url: String,
+    /// Returns the name of a player, the games only contain the UUID
+    pub name_url: Option<String>,
+    /// JSON pointer to the name in the response of `name_url` (Default: `/name`)
+    pub name_pointer: Option<String>,
+    /// Returns the most recent games, required for subscriptions
+    pub recent_games_url: Option<String>,
+    /// JSON pointer to the list of game ids in the response of `recent_games_url` (Default: the root)
+    pub recent_games_pointer: Option<String>,
+    pub auth: Option<AuthHeader>,
+    pub fields: FieldMapping,
+}
+
+/// Header sent with every request, e.g. `Authorization` with `Bearer {api_key}`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AuthHeader {
+    pub header: String,
+    pub value: String,
+}
+
+/// JSON pointers (RFC 6901, e.g. `/data/gameInfo/won`) into the game response for each field of `ApiData`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FieldMapping {
+    pub game_data: Option<String>,
+    /// Length of the game in milliseconds
+    pub time: String,
+    pub uuid: String,
+    pub won: String,
+    pub correct_flags: Option<String>,
+    pub incorrect_flags: Option<String>,
+    pub difficulty: Option<String>,
+    pub generator: Option<String>,
+    /// Name of the player if the game response contains it, `name_url` is not needed then
+    pub player_name: Option<String>,
+    pub player_group: Option<String>,
+    pub width: Option<String>,
+    pub height: Option<String>,
+    pub mines: Option<String>,
+    /// Unix timestamps in milliseconds
+    pub started_at: Option<String>,
+    pub ended_at: Option<String>,
+}
+
+pub struct JsonProvider {
+    id: String,
+    name: String,
+    base_url: String,
+    api_key_env: Option<String>,
+    config: JsonConfig,
+    http: HttpClient,
+}
+
+impl JsonProvider {
+    pub fn new(
+        id: String,
+        name: String,
+        base_url: Option<String>,
+        api_key_env: Option<String>,
+        config: JsonConfig,
+    ) -> JsonProvider {
+        JsonProvider {
+            id,
+            name,
+            base_url: base_url.unwrap_or_default(),
+            api_key_env,
+            config,
+            http: HttpClient::new(),
+        }
+    }
+
+    fn api_key(&self) -> String {
+        self.api_key_env
+            .as_ref()
+            .and_then(|env| std::env::var(env).ok())
+            .unwrap_or_default()
+    }
+
+    fn url(&self, template: &str) -> String {
+        template
+            .replace("{base_url}", self.base_url.as_str())
+            .replace("{api_key}", self.api_key().as_str())
+    }
+
+    fn get(&self, url: &str) -> Result<Value, MinesweeperError> {
+        let mut headers = Vec::new();
+
+        if let Some(auth) = &self.config.auth {
+            let api_key = self.api_key();
+            if auth.value.contains("{api_key}") && api_key.is_empty() {
+                return Err(MinesweeperError::ApiKeyNotFound);
+            }
+            headers.push((
+                auth.header.as_str(),
+                auth.value.replace("{api_key}", api_key.as_str()),
+            ));
+        }
+
+        let headers: Vec<(&str, &str)> = headers
+            .iter()
+            .map(|(header, value)| (*header, value.as_str()))
+            .collect();
+        let request_data = self.http.get(url, &headers)?;
+
+        serde_json::from_str(request_data.as_ref()).map_err(|_| MinesweeperError::ApiDataParse)
+    }
+}
+
+impl Provider for JsonProvider {
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn fetch_data(&self, gameid: &str) -> Result<ApiData, MinesweeperError> {
+        let response = self.get(&self.url(&self.config.game_url).replace("{game_id}", gameid))?;
+        map_api_data(&response, &self.config.fields)
+    }
+
+    fn fetch_name(&self, uuid: &str) -> Result<PlayerData, MinesweeperError> {
+        let name_url = self
+            .config
+            .name_url
+            .as_ref()
+            .ok_or(MinesweeperError::Unsupported)?;
+        let response = self.get(&self.url(name_url).replace("{uuid}", uuid))?;
+
+        let name = response
+            .pointer(self.config.name_pointer.as_deref().unwrap_or("/name"))
+            .and_then(as_string)
+            .ok_or(MinesweeperError::ApiDataParse)?;
+
+        Ok(PlayerData {
+            name,
+            group: None,
+            discord_user: None,
+            avatar_url: None,
+        })
+    }
+
+    fn supports_recent_games(&self) -> bool {
+        self.config.recent_games_url.is_some()
+    }
+
+    fn fetch_recent_games(&self) -> Result<Vec<String>, MinesweeperError> {
+        let recent_games_url = self
+            .config
+            .recent_games_url
+            .as_ref()
+            .ok_or(MinesweeperError::Unsupported)?;
+        let response = self.get(&self.url(recent_games_url))?;
+
+        response
+            .pointer(self.config.recent_games_pointer.as_deref().unwrap_or(""))
+            .and_then(Value::as_array)
+            .ok_or(MinesweeperError::ApiDataParse)?
+            .iter()
+            .map(|game_id| as_string(game_id).ok_or(MinesweeperError::ApiDataParse))
+            .collect()
+    }
+}
+
+/// Reads the fields of a game response, optional fields which are missing are left empty.
+pub fn map_api_data(response: &Value, fields: &FieldMapping) -> Result<ApiData, MinesweeperError> {
+    let optional = |pointer: &Option<String>| {
+        pointer
+            .as_ref()
+            .and_then(|pointer| response.pointer(pointer))
+            .filter(|value| !value.is_null())
+    };
+    let required = |pointer: &String| {
+        response
+            .pointer(pointer)
+            .ok_or(MinesweeperError::ApiDataParse)
+    };
+
+    Ok(ApiData {
+        game_data: optional(&fields.game_data).and_then(as_string),
+        tiepe: optional(&fields.difficulty).and_then(as_string),
+        time: as_u64(required(&fields.time)?).ok_or(MinesweeperError::ApiDataParse)?,
+        generator: optional(&fields.generator).and_then(as_string),
+        uuid: as_string(required(&fields.uuid)?).ok_or(MinesweeperError::ApiDataParse)?,
+        correct_flags: optional(&fields.correct_flags)
+            .and_then(as_u64)
+            .map(|x| x as u32),
+        incorrect_flags: optional(&fields.incorrect_flags)
+            .and_then(as_u64)
+            .map(|x| x as u32),
+        won: as_bool(required(&fields.won)?).ok_or(MinesweeperError::ApiDataParse)?,
+        player: optional(&fields.player_name)
+            .and_then(as_string)
+            .map(|name| PlayerData {
+                name,
+                group: optional(&fields.player_group).and_then(as_string),
+                discord_user: None,
+                avatar_url: None,
+            }),
+        size: optional(&fields.width)
+            .and_then(as_u64)
+            .zip(optional(&fields.height).and_then(as_u64))
+            .map(|(width, height)| (width as u32, height as u32)),
+        mines: optional(&fields.mines).and_then(as_u64).map(|x| x as u32),
+        started_at: optional(&fields.started_at).and_then(as_u64),
+        ended_at: optional(&fields.ended_at).and_then(as_u64),
+    })
+}
+
+/// Numbers are accepted as ids and names as well, APIs differ in what they send.
+fn as_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        _ => None,
+    }
+}
+
+fn as_u64(value: &Value) -> Option<u64> {
+    match value {
+        Value::Number(number) => number
+            .as_u64()
+            .or_else(|| number.as_f64().map(|x| x as u64)),
+        Value::String(text) => text.parse().ok(),
+        _ => None,
+    }
+}
+
+fn as_bool(value: &Value) -> Option<bool> {
+    match value {
+        Value::Bool(bool) => Some(*bool),
+        Value::Number(number) => number.as_u64().map(|x| x != 0),
+        Value::String(text) => text.parse().ok(),
+        _ => None,
+    }
+}
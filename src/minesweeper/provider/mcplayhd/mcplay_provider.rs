@@ -1,40 +1,92 @@
 use crate::minesweeper::base36;
 use crate::minesweeper::error::MinesweeperError;
-use crate::minesweeper::provider::provider::{ApiData, PlayerData, Provider};
+use crate::minesweeper::game_data::GameData;
+use crate::minesweeper::provider::http::HttpClient;
+use crate::minesweeper::provider::provider::{
+    ApiData, DisplayField, GameDisplay, PlayerData, Provider, find_after, without_scheme,
+};
 use serde::{Deserialize, Serialize};
 
-pub struct McPlayHdProvider;
+const DEFAULT_BASE_URL: &str = "https://mcplayhd.net/api/v1";
+
+pub struct McPlayHdProvider {
+    id: String,
+    name: String,
+    base_url: String,
+    api_key_env: Option<String>,
+    http: HttpClient,
+}
+
+impl McPlayHdProvider {
+    pub fn new(
+        id: String,
+        name: String,
+        base_url: Option<String>,
+        api_key_env: Option<String>,
+    ) -> McPlayHdProvider {
+        McPlayHdProvider {
+            id,
+            name,
+            base_url: base_url.unwrap_or(DEFAULT_BASE_URL.to_string()),
+            api_key_env,
+            http: HttpClient::new(),
+        }
+    }
+
+    fn api_key(&self) -> String {
+        self.api_key_env
+            .as_ref()
+            .and_then(|env| std::env::var(env).ok())
+            .unwrap_or_default()
+    }
+}
 
 impl Provider for McPlayHdProvider {
     fn id(&self) -> &str {
-        "mcplayhd"
+        self.id.as_str()
     }
 
     fn name(&self) -> &str {
-        "McPlayHD"
+        self.name.as_str()
     }
 
     fn fetch_data(&self, gameid: &str) -> Result<ApiData, MinesweeperError> {
-        let api_key = get_api_key();
+        let api_key = self.api_key();
         if String::is_empty(&api_key) {
             return Err(MinesweeperError::ApiKeyNotFound);
         }
 
-        let id = base36::decode(gameid);
+        let id = base36::decode(gameid)?;
 
-        let request_data =
-            ureq::get(format!("https://mcplayhd.net/api/v1/minesweeper/game/{id}").as_ref())
-                .set("Authorization", format!("Bearer {api_key}").as_str())
-                .call()
-                .map_err(|_| MinesweeperError::GameDataNotFound)?
-                .into_string()
-                .map_err(|_| MinesweeperError::GameDataNotFound)?;
+        let request_data = self.http.get(
+            format!("{}/minesweeper/game/{id}", self.base_url).as_str(),
+            &[("Authorization", format!("Bearer {api_key}").as_str())],
+        )?;
 
         let ms_data: Response = serde_json::from_str(request_data.as_ref())
             .map_err(|_| MinesweeperError::ApiDataParse)?;
 
+        //The players of the game are sent along, so no other API is asked for the name
+        let player = ms_data
+            .data
+            .players
+            .iter()
+            .find(|player| player.uuid == ms_data.data.game_info.uuid)
+            .or(ms_data.data.players.first())
+            .map(|player| PlayerData {
+                name: player.name.clone(),
+                group: Some(player.group.clone()).filter(|group| !group.is_empty()),
+                discord_user: None,
+                avatar_url: None,
+            });
+
         Ok(ApiData {
-            game_data: Some(ms_data.data.game_info.algebraic_notation.clone()),
+            game_data: ms_data
+                .data
+                .game_info
+                .algebraic_notation
+                .clone()
+                .filter(|notation| !notation.is_empty()),
             tiepe: None,
             time: ms_data.data.game_info.time_taken,
             generator: None,
@@ -42,22 +94,70 @@ impl Provider for McPlayHdProvider {
             correct_flags: Some(ms_data.data.game_info.flags_correct),
             incorrect_flags: Some(ms_data.data.game_info.flags_incorrect),
             won: ms_data.data.game_info.won,
+            player,
+            size: Some((ms_data.data.game_info.size_x, ms_data.data.game_info.size_z)),
+            mines: Some(ms_data.data.game_info.mines),
+            started_at: Some(ms_data.data.game_info.time_start),
+            ended_at: Some(ms_data.data.game_info.time_end),
         })
     }
 
-    fn fetch_name(&self, uuid: &str) -> Result<PlayerData, MinesweeperError> {
-        let request_data = ureq::get(format!("http://api.greev.eu/v2/player/name/{uuid}").as_ref())
-            .call()
-            .map_err(|_| MinesweeperError::GameDataNotFound)?
-            .into_string()
-            .map_err(|_| MinesweeperError::GameDataNotFound)?;
+    /// The player is part of the game response, McPlayHD has no API for names alone.
+    fn fetch_name(&self, _uuid: &str) -> Result<PlayerData, MinesweeperError> {
+        Err(MinesweeperError::Unsupported)
+    }
 
-        serde_json::from_str(request_data.as_ref()).map_err(|_| MinesweeperError::ApiDataParse)
+    fn display(&self, api_data: &ApiData, game_data: Option<&GameData>) -> GameDisplay {
+        GameDisplay {
+            fields: [
+                DisplayField::board(api_data),
+                Some(DisplayField::line_break()),
+                DisplayField::optional("Correct Flags", api_data.correct_flags),
+                DisplayField::optional("Incorrect Flags", api_data.incorrect_flags),
+                game_data.filter(|x| !x.summary).map(|game_data| {
+                    DisplayField::new(
+                        "Uncovered Fields",
+                        format!(
+                            "{}/{}",
+                            game_data.opened_fields,
+                            game_data.total_fields.saturating_sub(game_data.mine_count)
+                        ),
+                    )
+                }),
+            ]
+            .into_iter()
+            .flatten()
+            .collect(),
+            ..GameDisplay::default()
+        }
     }
-}
 
-fn get_api_key() -> String {
-    std::env::var("MCPLAYHD_API_KEY").unwrap_or_default()
+    fn is_valid_game_id(&self, game_id: &str) -> bool {
+        !game_id.is_empty()
+            && game_id
+                .chars()
+                .all(|c| c.is_ascii_digit() || c.is_ascii_lowercase())
+            && base36::decode(game_id).is_ok()
+    }
+
+    fn find_game_ids(&self, text: &str) -> Vec<String> {
+        let mut game_ids: Vec<String> = find_after(text, format!("{}:", self.id).as_str())
+            .into_iter()
+            .filter(|game_id| self.is_valid_game_id(game_id))
+            .collect();
+
+        //Links use the numeric id while the bot works with the base36 encoded one
+        game_ids.extend(
+            find_after(
+                text,
+                format!("{}/minesweeper/game/", without_scheme(&self.base_url)).as_str(),
+            )
+            .iter()
+            .filter_map(|id| id.parse::<i64>().ok())
+            .map(base36::encode),
+        );
+        game_ids
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -80,8 +180,9 @@ struct GameInfo {
     size_x: u32,
     #[serde(rename = "sizeZ")]
     size_z: u32,
-    #[serde(rename = "algebraicNotation")]
-    algebraic_notation: String,
+    /// Missing or empty for games without a replay, those are shown as a summary card
+    #[serde(rename = "algebraicNotation", default)]
+    algebraic_notation: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
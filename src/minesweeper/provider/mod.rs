@@ -1,3 +1,11 @@
 pub(crate) mod greev;
+pub mod http;
+pub(crate) mod json;
 pub(crate) mod mcplayhd;
+pub mod mojang;
+#[allow(clippy::module_inception)]
 pub mod provider;
+pub mod registry;
+pub mod skins;
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,29 @@
+use std::sync::LazyLock;
+
+use serde::{Deserialize, Serialize};
+
+use crate::minesweeper::error::MinesweeperError;
+use crate::minesweeper::provider::http::HttpClient;
+
+static MOJANG_HTTP: LazyLock<HttpClient> = LazyLock::new(HttpClient::new);
+
+#[derive(Serialize, Deserialize)]
+pub struct MojangProfile {
+    /// The UUID without dashes
+    pub id: String,
+    pub name: String,
+}
+
+/// Resolves a Minecraft name to the profile of the account currently using it.
+pub fn fetch_profile(name: &str) -> Result<MojangProfile, MinesweeperError> {
+    let request_data = MOJANG_HTTP.get(
+        format!("https://api.mojang.com/users/profiles/minecraft/{name}").as_str(),
+        &[],
+    )?;
+
+    serde_json::from_str(request_data.as_ref()).map_err(|_| MinesweeperError::ApiDataParse)
+}
+
+pub fn is_valid_name(name: &str) -> bool {
+    (3..=16).contains(&name.len()) && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
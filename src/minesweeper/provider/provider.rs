@@ -1,12 +1,133 @@
 use serde::{Deserialize, Serialize};
 
 use crate::minesweeper::error::MinesweeperError;
+use crate::minesweeper::game_data::GameData;
 
 pub trait Provider: Sync + Send {
     fn id(&self) -> &str;
     fn name(&self) -> &str;
     fn fetch_data(&self, gameid: &str) -> Result<ApiData, MinesweeperError>;
     fn fetch_name(&self, uuid: &str) -> Result<PlayerData, MinesweeperError>;
+    /// Whether the API lists recently played games, which subscriptions are polled from.
+    fn supports_recent_games(&self) -> bool {
+        false
+    }
+    /// Returns the ids of the most recently played games, newest first.
+    fn fetch_recent_games(&self) -> Result<Vec<String>, MinesweeperError> {
+        Err(MinesweeperError::Unsupported)
+    }
+    /// Whether the text has the syntax of a game id of this provider.
+    fn is_valid_game_id(&self, game_id: &str) -> bool {
+        !game_id.is_empty() && game_id.chars().all(|c| c.is_ascii_alphanumeric())
+    }
+    /// What the embed of a game shows besides the player, the time and the result.
+    /// Fields which the API did not send are left out.
+    fn display(&self, api_data: &ApiData, _game_data: Option<&GameData>) -> GameDisplay {
+        GameDisplay {
+            fields: vec![
+                DisplayField::optional("Difficulty", api_data.tiepe.as_ref()),
+                DisplayField::optional("Generator", api_data.generator.as_ref()),
+                DisplayField::board(api_data),
+                Some(DisplayField::line_break()),
+                DisplayField::optional("Correct Flags", api_data.correct_flags),
+                DisplayField::optional("Incorrect Flags", api_data.incorrect_flags),
+            ]
+            .into_iter()
+            .flatten()
+            .collect(),
+            ..GameDisplay::default()
+        }
+    }
+    /// Finds game ids mentioned in a text, written as `<provider id>:<game id>`.
+    /// Providers can extend this to also recognise links to their API.
+    fn find_game_ids(&self, text: &str) -> Vec<String> {
+        find_after(text, format!("{}:", self.id()).as_str())
+            .into_iter()
+            .filter(|game_id| self.is_valid_game_id(game_id))
+            .collect()
+    }
+}
+
+/// Everything a provider wants to show in the embed of a game.
+#[derive(Default)]
+pub struct GameDisplay {
+    pub fields: Vec<DisplayField>,
+    /// Overrides the colour of won and lost games as RGB
+    pub color: Option<(u8, u8, u8)>,
+    /// Put in front of the title instead of the provider name, if it is not the default provider
+    pub title_prefix: Option<String>,
+}
+
+pub struct DisplayField {
+    pub label: String,
+    pub value: String,
+    pub inline: bool,
+}
+
+impl DisplayField {
+    pub fn new(label: &str, value: impl ToString) -> DisplayField {
+        DisplayField {
+            label: label.to_string(),
+            value: value.to_string(),
+            inline: true,
+        }
+    }
+
+    /// Inline field which is only shown if the value is present.
+    pub fn optional(label: &str, value: Option<impl ToString>) -> Option<DisplayField> {
+        value.map(|value| DisplayField::new(label, value))
+    }
+
+    /// Size and mines of the board, if the provider sends them.
+    pub fn board(api_data: &ApiData) -> Option<DisplayField> {
+        let (width, height) = api_data.size?;
+        let mines = api_data.mines?;
+        Some(DisplayField::new(
+            "Board",
+            format!("{width}x{height}, {mines} mines"),
+        ))
+    }
+
+    /// Empty field which starts a new row of inline fields.
+    pub fn line_break() -> DisplayField {
+        DisplayField {
+            label: String::new(),
+            value: String::new(),
+            inline: false,
+        }
+    }
+
+    pub fn is_line_break(&self) -> bool {
+        self.label.is_empty() && self.value.is_empty() && !self.inline
+    }
+}
+
+/// Returns every id directly following `prefix` in the text, the prefix is matched case-insensitive.
+pub fn find_after(text: &str, prefix: &str) -> Vec<String> {
+    let lowercase_text = text.to_ascii_lowercase();
+    let prefix = prefix.to_ascii_lowercase();
+
+    lowercase_text
+        .match_indices(prefix.as_str())
+        .filter(|(index, _)| {
+            text[..*index]
+                .chars()
+                .next_back()
+                .is_none_or(|c| !c.is_alphanumeric())
+        })
+        .map(|(index, _)| {
+            text[index + prefix.len()..]
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+                .collect::<String>()
+        })
+        .filter(|id| !id.is_empty())
+        .collect()
+}
+
+/// Links are matched with and without `https://`, so the scheme of a base URL is dropped.
+pub fn without_scheme(url: &str) -> &str {
+    url.split_once("://").map(|(_, rest)| rest).unwrap_or(url)
 }
 
 #[derive(Serialize, Deserialize)]
@@ -15,6 +136,7 @@ pub struct ApiData {
     pub game_data: Option<String>,
     #[serde(rename = "type")]
     pub tiepe: Option<String>,
+    /// How long the game took in milliseconds, `started_at` tells when it was played
     pub time: u64,
     pub generator: Option<String>,
     pub uuid: String,
@@ -23,9 +145,33 @@ pub struct ApiData {
     #[serde(rename = "incorrectFlags")]
     pub incorrect_flags: Option<u32>,
     pub won: bool,
+    /// Filled in by providers which send the player together with the game, saving a name lookup
+    #[serde(skip)]
+    pub player: Option<PlayerData>,
+    /// Columns and rows of the board, known even without game data if the provider sends them
+    #[serde(skip)]
+    pub size: Option<(u32, u32)>,
+    #[serde(skip)]
+    pub mines: Option<u32>,
+    /// Unix timestamp in milliseconds
+    #[serde(skip)]
+    pub started_at: Option<u64>,
+    /// Unix timestamp in milliseconds
+    #[serde(skip)]
+    pub ended_at: Option<u64>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct PlayerData {
     pub name: String,
+    /// Rank of the player on the server, if the provider sends one
+    #[serde(default)]
+    pub group: Option<String>,
+    /// The Discord user who linked this Minecraft account, filled in by the bot.
+    #[serde(skip)]
+    pub discord_user: Option<u64>,
+    /// Face of the player at the skin service, filled in by the bot unless the service recently
+    /// failed. Embeds have no placeholder, the thumbnail is left out instead.
+    #[serde(skip)]
+    pub avatar_url: Option<String>,
 }
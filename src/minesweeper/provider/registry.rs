@@ -0,0 +1,232 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serenity::client::Context;
+use serenity::prelude::TypeMapKey;
+
+use crate::minesweeper::error::MinesweeperError;
+use crate::minesweeper::provider::greev::greev_provider::GreevProvider;
+use crate::minesweeper::provider::json::json_provider::{JsonConfig, JsonProvider};
+use crate::minesweeper::provider::mcplayhd::mcplay_provider::McPlayHdProvider;
+use crate::minesweeper::provider::provider::Provider;
+
+const DEFAULT_PROVIDERS_PATH: &str = "providers.json";
+
+pub const KIND_GREEV: &str = "greev";
+pub const KIND_MCPLAYHD: &str = "mcplayhd";
+pub const KIND_JSON: &str = "json";
+
+/// The providers the bot can fetch games from, see `providers.example.json`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RegistryConfig {
+    /// Used when a command is run without choosing a provider
+    pub default_provider: String,
+    pub providers: Vec<ProviderConfig>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProviderConfig {
+    /// Used in commands, subscriptions and the history, e.g. `greev` in `greev:1a2b3c`
+    pub id: String,
+    /// Which API the provider speaks, `greev`, `mcplayhd` or `json`
+    pub kind: String,
+    /// Shown in choices and embeds (Default: the id)
+    pub name: Option<String>,
+    /// Base URL of the API without trailing slash (Default: the public API of the kind)
+    pub base_url: Option<String>,
+    /// Environment variable holding the API key, for APIs which need one
+    pub api_key_env: Option<String>,
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+    /// URLs and field mappings of a `json` provider
+    pub json: Option<JsonConfig>,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+impl Default for RegistryConfig {
+    /// Greev and McPlayHD with their public APIs, used if no config file exists.
+    fn default() -> Self {
+        RegistryConfig {
+            default_provider: "greev".to_string(),
+            providers: vec![
+                ProviderConfig {
+                    id: "greev".to_string(),
+                    kind: KIND_GREEV.to_string(),
+                    name: Some("Greev".to_string()),
+                    base_url: None,
+                    api_key_env: None,
+                    enabled: true,
+                    json: None,
+                },
+                ProviderConfig {
+                    id: "mcplayhd".to_string(),
+                    kind: KIND_MCPLAYHD.to_string(),
+                    name: Some("McPlayHD".to_string()),
+                    base_url: None,
+                    api_key_env: Some("MCPLAYHD_API_KEY".to_string()),
+                    enabled: true,
+                    json: None,
+                },
+            ],
+        }
+    }
+}
+
+pub struct ProviderRegistry {
+    providers: Vec<Box<dyn Provider>>,
+    default_provider: String,
+}
+
+pub struct ProviderRegistryKey;
+
+impl TypeMapKey for ProviderRegistryKey {
+    type Value = Arc<ProviderRegistry>;
+}
+
+impl ProviderRegistry {
+    /// Loads the providers from the path in `PROVIDERS_PATH` (Default: `providers.json`).
+    /// Without a config file Greev and McPlayHD are used.
+    pub fn load() -> Result<ProviderRegistry, MinesweeperError> {
+        let path = std::env::var("PROVIDERS_PATH").unwrap_or(DEFAULT_PROVIDERS_PATH.to_string());
+
+        let config = if Path::new(&path).exists() {
+            let raw = std::fs::read_to_string(&path)
+                .map_err(|_| MinesweeperError::InvalidProviderConfig)?;
+            serde_json::from_str(raw.as_str())
+                .map_err(|_| MinesweeperError::InvalidProviderConfig)?
+        } else {
+            RegistryConfig::default()
+        };
+
+        ProviderRegistry::from_config(config)
+    }
+
+    pub fn from_config(config: RegistryConfig) -> Result<ProviderRegistry, MinesweeperError> {
+        let mut providers: Vec<Box<dyn Provider>> = Vec::new();
+
+        for provider in config.providers.into_iter().filter(|x| x.enabled) {
+            if !provider
+                .id
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+                || providers.iter().any(|x| x.id() == provider.id)
+            {
+                return Err(MinesweeperError::InvalidProviderConfig);
+            }
+
+            let name = provider.name.unwrap_or(provider.id.clone());
+            providers.push(match provider.kind.as_str() {
+                KIND_GREEV => Box::new(GreevProvider::new(provider.id, name, provider.base_url)),
+                KIND_MCPLAYHD => Box::new(McPlayHdProvider::new(
+                    provider.id,
+                    name,
+                    provider.base_url,
+                    provider.api_key_env,
+                )),
+                KIND_JSON => Box::new(JsonProvider::new(
+                    provider.id,
+                    name,
+                    provider.base_url,
+                    provider.api_key_env,
+                    provider
+                        .json
+                        .ok_or(MinesweeperError::InvalidProviderConfig)?,
+                )),
+                _ => return Err(MinesweeperError::InvalidProviderConfig),
+            });
+        }
+
+        if !providers.iter().any(|x| x.id() == config.default_provider) {
+            return Err(MinesweeperError::InvalidProviderConfig);
+        }
+
+        Ok(ProviderRegistry {
+            providers,
+            default_provider: config.default_provider,
+        })
+    }
+
+    /// All enabled providers in the order of the config.
+    pub fn providers(&self) -> Vec<&dyn Provider> {
+        self.providers.iter().map(|x| x.as_ref()).collect()
+    }
+
+    pub fn get(&self, id: &str) -> Option<&dyn Provider> {
+        self.providers
+            .iter()
+            .find(|x| x.id() == id.to_lowercase())
+            .map(|x| x.as_ref())
+    }
+
+    /// The chosen provider or the default one if none was chosen.
+    pub fn get_or_default(&self, id: Option<&str>) -> Option<&dyn Provider> {
+        self.get(id.unwrap_or(self.default_provider.as_str()))
+    }
+
+    pub fn default_provider(&self) -> &dyn Provider {
+        self.get(self.default_provider.as_str())
+            .expect("The default provider is checked when loading the registry")
+    }
+
+    pub fn is_default(&self, provider: &dyn Provider) -> bool {
+        provider.id() == self.default_provider
+    }
+
+    /// Game ids and links of every provider found in the text, ids which are not valid for
+    /// their provider are dropped before anything is fetched.
+    pub fn find_games(&self, text: &str) -> Vec<(&dyn Provider, String)> {
+        self.providers()
+            .into_iter()
+            .flat_map(|provider| {
+                provider
+                    .find_game_ids(text)
+                    .into_iter()
+                    .filter(|game_id| provider.is_valid_game_id(game_id))
+                    .map(move |game_id| (provider, game_id))
+            })
+            .collect()
+    }
+
+    /// A text consisting of nothing but a valid id, treated as a game of the default provider.
+    pub fn bare_game(&self, text: &str) -> Option<(&dyn Provider, String)> {
+        let game_id = text.trim();
+        let provider = self.default_provider();
+
+        provider
+            .is_valid_game_id(game_id)
+            .then(|| (provider, game_id.to_string()))
+    }
+}
+
+pub async fn get_registry(ctx: &Context) -> Arc<ProviderRegistry> {
+    ctx.data
+        .read()
+        .await
+        .get::<ProviderRegistryKey>()
+        .expect("Provider registry was not initialized")
+        .clone()
+}
+
+/// Providers wait for their API and sleep between retries, so their requests are sent from the
+/// blocking thread pool instead of holding up a worker of the async runtime.
+pub async fn fetch_blocking<T: Send + 'static>(
+    ctx: &Context,
+    provider: &dyn Provider,
+    fetch: impl FnOnce(&dyn Provider) -> Result<T, MinesweeperError> + Send + 'static,
+) -> Result<T, MinesweeperError> {
+    let registry = get_registry(ctx).await;
+    let provider_id = provider.id().to_string();
+
+    tokio::task::spawn_blocking(move || {
+        let provider = registry
+            .get(provider_id.as_str())
+            .ok_or(MinesweeperError::Unsupported)?;
+        fetch(provider)
+    })
+    .await
+    .map_err(|_| MinesweeperError::UpstreamDown)?
+}
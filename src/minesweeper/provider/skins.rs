@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use image::{Rgba, RgbaImage};
+
+use crate::minesweeper::provider::http::HttpClient;
+
+/// `{uuid}` is replaced with the UUID of the player, the service has to return the face as image
+const DEFAULT_SKIN_URL: &str = "https://mc-heads.net/avatar/{uuid}/64";
+/// Skins rarely change, so faces are kept for a while
+const CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+/// Players whose face could not be fetched are not asked for again before this
+const FAILURE_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// Face shown if the skin service is unreachable, `H` hair, `S` skin, `W` white, `E` eye and `M` mouth.
+const PLACEHOLDER_HEAD: [&str; 8] = [
+    "HHHHHHHH", "HHHHHHHH", "HSSSSSSH", "SSSSSSSS", "SWESSEWS", "SSSMMSSS", "SSMSSMSS", "SSMMMMSS",
+];
+
+static SKINS: LazyLock<SkinService> = LazyLock::new(SkinService::load);
+
+struct SkinService {
+    url: String,
+    http: HttpClient,
+    cache: Mutex<HashMap<String, CachedHead>>,
+}
+
+struct CachedHead {
+    fetched_at: Instant,
+    /// `None` if the service did not deliver the face
+    head: Option<RgbaImage>,
+}
+
+impl SkinService {
+    /// Uses the URL template in `SKIN_URL` (Default: mc-heads.net).
+    fn load() -> SkinService {
+        SkinService {
+            url: std::env::var("SKIN_URL").unwrap_or(DEFAULT_SKIN_URL.to_string()),
+            http: HttpClient::new(),
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn url(&self, uuid: &str) -> String {
+        self.url.replace("{uuid}", uuid)
+    }
+
+    fn fetch(&self, uuid: &str) -> Option<RgbaImage> {
+        let bytes = self.http.get_bytes(self.url(uuid).as_str(), &[]).ok()?;
+        image::load_from_memory(&bytes)
+            .ok()
+            .map(|image| image.to_rgba8())
+    }
+}
+
+/// URL of the face of the player at the skin service, for embeds.
+pub fn head_url(uuid: &str) -> String {
+    SKINS.url(uuid)
+}
+
+/// The face of the player, `None` if the skin service is unreachable or does not know the player.
+pub fn fetch_head(uuid: &str) -> Option<RgbaImage> {
+    {
+        let mut cache = SKINS.cache.lock().expect("Skin cache was poisoned");
+        cache.retain(|_, cached| {
+            let ttl = if cached.head.is_some() {
+                CACHE_TTL
+            } else {
+                FAILURE_TTL
+            };
+            cached.fetched_at.elapsed() < ttl
+        });
+
+        if let Some(cached) = cache.get(uuid) {
+            return cached.head.clone();
+        }
+    }
+
+    //The cache is not locked while fetching, so a slow service does not block cached players
+    let head = SKINS.fetch(uuid);
+    SKINS.cache.lock().expect("Skin cache was poisoned").insert(
+        uuid.to_string(),
+        CachedHead {
+            fetched_at: Instant::now(),
+            head: head.clone(),
+        },
+    );
+
+    head
+}
+
+/// Whether the face of the player can be linked, decided from earlier requests without sending one.
+/// It is not if the service is down or failed for this player within `FAILURE_TTL`.
+pub fn is_reachable(uuid: &str) -> bool {
+    if SKINS.http.is_open() {
+        return false;
+    }
+
+    let cache = SKINS.cache.lock().expect("Skin cache was poisoned");
+    cache
+        .get(uuid)
+        .is_none_or(|cached| cached.head.is_some() || cached.fetched_at.elapsed() >= FAILURE_TTL)
+}
+
+pub fn head_or_placeholder(uuid: &str) -> RgbaImage {
+    fetch_head(uuid).unwrap_or_else(placeholder_head)
+}
+
+/// 8x8 face drawn locally, scale it with `FilterType::Nearest` to keep the pixels sharp.
+pub fn placeholder_head() -> RgbaImage {
+    RgbaImage::from_fn(8, 8, |x, y| {
+        match PLACEHOLDER_HEAD[y as usize].as_bytes()[x as usize] {
+            b'H' => Rgba([59, 40, 20, 255]),
+            b'W' => Rgba([255, 255, 255, 255]),
+            b'E' => Rgba([73, 61, 135, 255]),
+            b'M' => Rgba([112, 67, 52, 255]),
+            _ => Rgba([184, 134, 100, 255]),
+        }
+    })
+}
+
+/// Whether the player is drawn above rendered games, enabled with `SKIN_HUD=true`.
+pub fn hud_enabled() -> bool {
+    std::env::var("SKIN_HUD").is_ok_and(|value| value == "true")
+}
@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+
+/// A response the mock server sends for a path.
+#[derive(Clone)]
+pub struct Route {
+    pub status: u16,
+    pub body: String,
+    /// The request is answered with 401 unless this `Authorization` header is sent
+    pub authorization: Option<String>,
+}
+
+impl Route {
+    pub fn json(body: &str) -> Route {
+        Route {
+            status: 200,
+            body: body.to_string(),
+            authorization: None,
+        }
+    }
+
+    pub fn with_authorization(mut self, authorization: &str) -> Route {
+        self.authorization = Some(authorization.to_string());
+        self
+    }
+}
+
+/// Local stand-in for a provider API, paths without a route are answered with 404.
+pub struct MockServer {
+    port: u16,
+}
+
+impl MockServer {
+    /// Serves the routes on a free port until the tests end.
+    pub fn start(routes: Vec<(&str, Route)>) -> MockServer {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Unable to bind the mock server");
+        let port = listener.local_addr().unwrap().port();
+        let routes: HashMap<String, Route> = routes
+            .into_iter()
+            .map(|(path, route)| (path.to_string(), route))
+            .collect();
+
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                respond(stream, &routes);
+            }
+        });
+
+        MockServer { port }
+    }
+
+    /// Base URL of the server followed by the path prefix of the API, e.g. `/v2`.
+    pub fn url(&self, prefix: &str) -> String {
+        format!("http://127.0.0.1:{}{prefix}", self.port)
+    }
+}
+
+fn respond(mut stream: TcpStream, routes: &HashMap<String, Route>) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+    let path = request_line
+        .split(' ')
+        .nth(1)
+        .unwrap_or_default()
+        .to_string();
+
+    let mut authorization = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).unwrap_or(0) == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((_, value)) = line
+            .split_once(':')
+            .filter(|(name, _)| name.eq_ignore_ascii_case("authorization"))
+        {
+            authorization = Some(value.trim().to_string());
+        }
+    }
+
+    let (status, body) = match routes.get(&path) {
+        Some(route) if route.authorization.is_some() && route.authorization != authorization => {
+            (401, String::new())
+        }
+        Some(route) => (route.status, route.body.clone()),
+        None => (404, String::new()),
+    };
+
+    let _ = write!(
+        stream,
+        "HTTP/1.1 {status} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+}
@@ -0,0 +1,216 @@
+//! Providers against a local stand-in of their APIs, serving recorded responses from `fixtures`.
+
+use crate::commands::error::CommandError;
+use crate::commands::render::get_image_data;
+use crate::minesweeper::base36;
+use crate::minesweeper::error::MinesweeperError;
+use crate::minesweeper::provider::provider::{ApiData, Provider};
+use crate::minesweeper::provider::registry::{
+    KIND_GREEV, KIND_MCPLAYHD, ProviderConfig, ProviderRegistry, RegistryConfig,
+};
+
+use mock_server::{MockServer, Route};
+
+mod mock_server;
+
+const GREEV_GAME: &str = include_str!("fixtures/greev_game.json");
+const GREEV_NAME: &str = include_str!("fixtures/greev_name.json");
+const MCPLAYHD_GAME: &str = include_str!("fixtures/mcplayhd_game.json");
+const BROKEN: &str = include_str!("fixtures/broken.json");
+
+const UUID: &str = "8c3a1b6e4f2d4c7a9e5b0d1f2a3b4c5d";
+const MCPLAYHD_ID: i64 = 1337;
+
+/// Cargo sets this for every test run, so the API key needs no changes to the environment.
+const API_KEY_ENV: &str = "CARGO_PKG_NAME";
+const API_KEY: &str = env!("CARGO_PKG_NAME");
+
+fn registry(kind: &str, server: &MockServer, api_key_env: Option<&str>) -> ProviderRegistry {
+    let prefix = if kind == KIND_GREEV { "/v2" } else { "/api/v1" };
+    ProviderRegistry::from_config(RegistryConfig {
+        default_provider: "mock".to_string(),
+        providers: vec![ProviderConfig {
+            id: "mock".to_string(),
+            kind: kind.to_string(),
+            name: Some("Mock".to_string()),
+            base_url: Some(server.url(prefix)),
+            api_key_env: api_key_env.map(str::to_string),
+            enabled: true,
+            json: None,
+        }],
+    })
+    .unwrap()
+}
+
+fn greev_server(game: &str) -> MockServer {
+    MockServer::start(vec![
+        ("/v2/stats/minesweeper/game/a1b2c3", Route::json(game)),
+        (
+            format!("/v2/player/name/{UUID}").as_str(),
+            Route::json(GREEV_NAME),
+        ),
+    ])
+}
+
+fn mcplayhd_server(game: &str) -> MockServer {
+    MockServer::start(vec![(
+        format!("/api/v1/minesweeper/game/{MCPLAYHD_ID}").as_str(),
+        Route::json(game).with_authorization(format!("Bearer {API_KEY}").as_str()),
+    )])
+}
+
+async fn fetch_and_render(provider: &dyn Provider, game_id: &str) -> ApiData {
+    let api_data = provider.fetch_data(game_id).unwrap();
+    assert_eq!(api_data.uuid, UUID);
+    assert!(api_data.won);
+    assert_eq!(api_data.correct_flags, Some(0));
+
+    let game_data = get_image_data(&api_data, None, &false, &false)
+        .await
+        .unwrap();
+    assert!(!game_data.summary, "The fixture contains replay data");
+    assert!(!game_data.image_data.is_empty());
+    assert_eq!(game_data.mine_count, 4);
+
+    api_data
+}
+
+#[tokio::test]
+async fn renders_greev_game() {
+    let server = greev_server(GREEV_GAME);
+    let registry = registry(KIND_GREEV, &server, None);
+    let provider = registry.default_provider();
+
+    let api_data = fetch_and_render(provider, "a1b2c3").await;
+    assert!(api_data.player.is_none());
+    assert_eq!(provider.fetch_name(UUID).unwrap().name, "Steve");
+}
+
+#[test]
+fn unknown_greev_game_is_not_found() {
+    let server = greev_server(GREEV_GAME);
+    let registry = registry(KIND_GREEV, &server, None);
+
+    let Err(error) = registry.default_provider().fetch_data("ffffff") else {
+        panic!("The game does not exist");
+    };
+    assert!(matches!(error, MinesweeperError::GameDataNotFound));
+    assert!(matches!(
+        CommandError::from_fetch("Mock", error),
+        CommandError::GameNotFound
+    ));
+}
+
+#[test]
+fn broken_greev_response_is_rejected() {
+    let server = greev_server(BROKEN);
+    let registry = registry(KIND_GREEV, &server, None);
+
+    assert!(matches!(
+        registry.default_provider().fetch_data("a1b2c3"),
+        Err(MinesweeperError::ApiDataParse)
+    ));
+}
+
+#[tokio::test]
+async fn renders_mcplayhd_game() {
+    let server = mcplayhd_server(MCPLAYHD_GAME);
+    let registry = registry(KIND_MCPLAYHD, &server, Some(API_KEY_ENV));
+
+    let provider = registry.default_provider();
+
+    let api_data = fetch_and_render(provider, &base36::encode(MCPLAYHD_ID)).await;
+    let player = api_data
+        .player
+        .expect("McPlayHD sends the players of a game");
+    assert_eq!(player.name, "Steve");
+    assert_eq!(player.group.as_deref(), Some("default"));
+    assert_eq!(api_data.size, Some((30, 16)));
+    assert_eq!(api_data.mines, Some(4));
+    assert_eq!(api_data.ended_at, Some(1760000003000));
+
+    //Names are not looked up at other servers
+    assert!(matches!(
+        provider.fetch_name(UUID),
+        Err(MinesweeperError::Unsupported)
+    ));
+}
+
+#[test]
+fn mcplayhd_game_without_replay_has_no_game_data() {
+    let replay = r#""algebraicNotation": "1=30x16+0TF0FT8F+005+T03P""#;
+    let empty = MCPLAYHD_GAME.replace(replay, r#""algebraicNotation": """#);
+    let missing = MCPLAYHD_GAME.replace(format!(",\n      {replay}").as_str(), "");
+    assert_ne!(missing, MCPLAYHD_GAME);
+
+    for game in [empty, missing] {
+        let server = mcplayhd_server(&game);
+        let registry = registry(KIND_MCPLAYHD, &server, Some(API_KEY_ENV));
+
+        let api_data = registry
+            .default_provider()
+            .fetch_data(&base36::encode(MCPLAYHD_ID))
+            .unwrap();
+        assert!(api_data.game_data.is_none(), "{game}");
+        assert_eq!(api_data.size, Some((30, 16)));
+        assert_eq!(api_data.mines, Some(4));
+    }
+}
+
+#[test]
+fn unknown_mcplayhd_game_is_not_found() {
+    let server = mcplayhd_server(MCPLAYHD_GAME);
+    let registry = registry(KIND_MCPLAYHD, &server, Some(API_KEY_ENV));
+
+    assert!(matches!(
+        registry
+            .default_provider()
+            .fetch_data(&base36::encode(MCPLAYHD_ID + 1)),
+        Err(MinesweeperError::GameDataNotFound)
+    ));
+}
+
+#[test]
+fn broken_mcplayhd_response_is_rejected() {
+    let server = mcplayhd_server(BROKEN);
+    let registry = registry(KIND_MCPLAYHD, &server, Some(API_KEY_ENV));
+
+    assert!(matches!(
+        registry
+            .default_provider()
+            .fetch_data(&base36::encode(MCPLAYHD_ID)),
+        Err(MinesweeperError::ApiDataParse)
+    ));
+}
+
+#[test]
+fn mcplayhd_requires_api_key() {
+    let server = mcplayhd_server(MCPLAYHD_GAME);
+    let registry = registry(KIND_MCPLAYHD, &server, Some("MS_RENDERER_UNSET_API_KEY"));
+
+    let Err(error) = registry
+        .default_provider()
+        .fetch_data(&base36::encode(MCPLAYHD_ID))
+    else {
+        panic!("No API key is set");
+    };
+    assert!(matches!(error, MinesweeperError::ApiKeyNotFound));
+    assert_eq!(
+        CommandError::from_fetch("Mock", error).to_string(),
+        "The bot is not allowed to use the Mock API."
+    );
+}
+
+#[test]
+fn mcplayhd_rejects_wrong_api_key() {
+    let server = mcplayhd_server(MCPLAYHD_GAME);
+    // The name of the crate is the expected key, any other variable of cargo is a wrong one
+    let registry = registry(KIND_MCPLAYHD, &server, Some("CARGO_PKG_VERSION"));
+
+    assert!(matches!(
+        registry
+            .default_provider()
+            .fetch_data(&base36::encode(MCPLAYHD_ID)),
+        Err(MinesweeperError::Unauthorized)
+    ));
+}
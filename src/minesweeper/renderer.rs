@@ -3,40 +3,38 @@ use std::io::Cursor;
 use std::time::Duration;
 
 use crate::minesweeper::error::MinesweeperError;
+use crate::minesweeper::font::draw_text;
 use crate::minesweeper::minesweeper_logic::{Board, FieldState};
-use crate::minesweeper::parsers::parser::{ActionType, FlagAction, Metadata, OpenAction};
+use crate::minesweeper::parsers::parser::{
+    ActionType, ChordAction, FlagAction, Metadata, OpenAction, TimedAction, timeline,
+};
 use crate::minesweeper::textures::load_textures;
 use gif::{Encoder, Frame as GifFrame, Repeat};
-use image::{Delay, DynamicImage, Frame, GenericImage, ImageBuffer, Rgba};
+use image::{Delay, DynamicImage, Frame, GenericImage, ImageBuffer, Rgba, RgbaImage, imageops};
+
+/// Height of the strip above the board showing the player
+const HUD_HEIGHT: u32 = 40;
 
 pub struct Renderer {
     pub(crate) metadata: Metadata,
     pub(crate) game_board: Board,
     pub open_data: Vec<OpenAction>,
     pub flag_data: Vec<FlagAction>,
+    pub chord_data: Vec<ChordAction>,
     image_data: Imagedata,
     full: bool,
+    /// Fields tinted in the next frame, e.g. the area of a chord
+    highlighted_fields: Vec<(u32, u32)>,
+    hud: Option<Hud>,
 }
 
-#[derive(Copy, Clone)]
-pub enum RenderType {
-    Image,
-    Gif,
+/// The player drawn above the board.
+pub struct Hud {
+    pub head: RgbaImage,
+    pub name: String,
 }
 
-impl std::str::FromStr for RenderType {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.trim().to_lowercase().as_ref() {
-            "image" => Ok(RenderType::Image),
-            "gif" => Ok(RenderType::Gif),
-            _ => Err(format!("Unknown render type: {}", s)),
-        }
-    }
-}
-
-struct Imagedata {
+pub(crate) struct Imagedata {
     zero: ImageBuffer<Rgba<u8>, Vec<u8>>,
     one: ImageBuffer<Rgba<u8>, Vec<u8>>,
     two: ImageBuffer<Rgba<u8>, Vec<u8>>,
@@ -46,9 +44,9 @@ struct Imagedata {
     six: ImageBuffer<Rgba<u8>, Vec<u8>>,
     seven: ImageBuffer<Rgba<u8>, Vec<u8>>,
     eight: ImageBuffer<Rgba<u8>, Vec<u8>>,
-    tnt: ImageBuffer<Rgba<u8>, Vec<u8>>,
+    pub(crate) tnt: ImageBuffer<Rgba<u8>, Vec<u8>>,
     empty: ImageBuffer<Rgba<u8>, Vec<u8>>,
-    flag: ImageBuffer<Rgba<u8>, Vec<u8>>,
+    pub(crate) flag: ImageBuffer<Rgba<u8>, Vec<u8>>,
     unsure_flag: ImageBuffer<Rgba<u8>, Vec<u8>>,
 }
 
@@ -94,6 +92,7 @@ impl Renderer {
         game_board: Board,
         open_data: Vec<OpenAction>,
         flag_data: Vec<FlagAction>,
+        chord_data: Vec<ChordAction>,
         gif: &bool,
         full: &bool,
     ) -> Renderer {
@@ -102,23 +101,81 @@ impl Renderer {
             game_board,
             open_data,
             flag_data,
+            chord_data,
             image_data: Imagedata::new(load_textures(gif).as_slice()),
             full: *full,
+            highlighted_fields: Vec::new(),
+            hud: None,
         }
     }
 
+    /// Draws the player above the board in jpeg and gif renders.
+    pub fn with_hud(mut self, hud: Hud) -> Renderer {
+        self.hud = Some(hud);
+        self
+    }
+
+    /// Renders the last frame of the gif, the actions are performed in the same order.
     pub fn render_jpeg(&mut self) -> Result<Vec<u8>, MinesweeperError> {
-        self.flag_data
-            .iter()
-            .for_each(|action| action.perform_action(&mut self.game_board));
+        let actions = timeline(&self.flag_data, &self.open_data, &self.chord_data);
+        let last_tick = actions.last().map(|action| action.total_time());
+
+        //Like in the last frame of the gif, the chords of the last tick are highlighted
+        let mut last_chords = Vec::new();
+        for action in actions {
+            match action {
+                TimedAction::Flag(flag) => flag.perform_action(&mut self.game_board),
+                TimedAction::Open(open) => {
+                    self.game_board.open_field(open.x as usize, open.y as usize)
+                }
+                TimedAction::Chord(chord) => {
+                    self.game_board.chord(chord.x as usize, chord.y as usize);
+                    if Some(chord.total_time) == last_tick {
+                        last_chords.push((chord.x, chord.y));
+                    }
+                }
+            }
+        }
 
-        self.open_data.iter().for_each(|action| {
-            self.game_board
-                .open_field(action.x as usize, action.y as usize);
-        });
+        for (x, y) in last_chords {
+            self.highlight_area(x, y);
+        }
 
+        self.render_current_state()
+    }
+
+    /// Renders the board as it is, without performing the actions first.
+    pub fn render_current_state(&mut self) -> Result<Vec<u8>, MinesweeperError> {
         let percentage_done = self.game_board.calculate_done_percentage();
         let frame = self.generate_image(percentage_done)?;
+        let frame = self.add_hud(frame)?;
+
+        let mut buffer = Cursor::new(vec![]);
+
+        DynamicImage::ImageRgba8(frame)
+            .write_to(&mut buffer, image::ImageFormat::WebP)
+            .unwrap();
+
+        Ok(buffer.into_inner())
+    }
+
+    /// Renders the board as it is and tints the given `(x, y, count)` fields red,
+    /// the more often a field was counted the stronger the tint.
+    pub fn render_heatmap(
+        &mut self,
+        fields: &[(i32, i32, u32)],
+    ) -> Result<Vec<u8>, MinesweeperError> {
+        let mut frame = self.generate_image(100)?;
+        let max_count = fields.iter().map(|x| x.2).max().unwrap_or(1).max(1);
+
+        for &(x, y, count) in fields {
+            if x < 0 || x >= self.metadata.x_size || y < 0 || y >= self.metadata.y_size {
+                continue;
+            }
+
+            let strength = 0.25 + 0.5 * (count as f32 / max_count as f32);
+            tint_field(&mut frame, x as u32, y as u32, [229, 57, 53], strength);
+        }
 
         let mut buffer = Cursor::new(vec![]);
 
@@ -135,6 +192,7 @@ impl Renderer {
         let tick_map: BTreeMap<i64, Vec<ActionType>> = self.create_tick_map();
 
         let frame = self.generate_image(0)?;
+        let frame = self.add_hud(frame)?;
         frames.push(Frame::from_parts(
             frame,
             0,
@@ -173,11 +231,28 @@ impl Renderer {
                 self.open_data.retain(|open| open.total_time.gt(tick.0))
             }
 
+            if tick.1.contains(&ActionType::Chord) {
+                let chords: Vec<(i32, i32)> = self
+                    .chord_data
+                    .iter()
+                    .filter(|chord| chord.total_time.eq(tick.0))
+                    .map(|chord| (chord.x, chord.y))
+                    .collect();
+
+                for (x, y) in chords {
+                    self.game_board.chord(x as usize, y as usize);
+                    self.highlight_area(x, y);
+                }
+
+                self.chord_data.retain(|chord| chord.total_time.gt(tick.0))
+            }
+
             let frame = self.generate_image(if id == (tick_map.len() - 1) {
                 100
             } else {
                 ((id as f32 / tick_map.len() as f32) * 100.0) as u32
             })?;
+            let frame = self.add_hud(frame)?;
 
             frames.push(Frame::from_parts(
                 frame,
@@ -190,6 +265,30 @@ impl Renderer {
         self.encode_frames_to_gif(frames)
     }
 
+    /// Puts the frame below a strip with the head and name of the player, if there is a HUD.
+    fn add_hud(&self, frame: RgbaImage) -> Result<RgbaImage, MinesweeperError> {
+        let Some(hud) = &self.hud else {
+            return Ok(frame);
+        };
+
+        let mut image = ImageBuffer::from_pixel(
+            frame.width(),
+            frame.height() + HUD_HEIGHT,
+            Rgba([43, 45, 49, 255]),
+        );
+        image
+            .copy_from(&frame, 0, HUD_HEIGHT)
+            .map_err(|_| MinesweeperError::ImageInsertion)?;
+
+        let head = imageops::resize(&hud.head, 32, 32, imageops::FilterType::Nearest);
+        image
+            .copy_from(&head, 4, 4)
+            .map_err(|_| MinesweeperError::ImageInsertion)?;
+        draw_text(&mut image, 44, 13, &hud.name, 2, Rgba([230, 230, 230, 255]));
+
+        Ok(image)
+    }
+
     fn encode_frames_to_gif(&mut self, frames: Vec<Frame>) -> Result<Vec<u8>, MinesweeperError> {
         if frames.is_empty() {
             return Err(MinesweeperError::NoFrames);
@@ -205,7 +304,7 @@ impl Renderer {
             .set_repeat(Repeat::Infinite)
             .map_err(|_| MinesweeperError::GifEncoding)?;
 
-        for (_i, image) in frames.into_iter().enumerate() {
+        for image in frames.into_iter() {
             let frame_delay = image.delay().numer_denom_ms().0 / 10;
             let rbga_frame = &mut image.into_buffer();
             let mut frame = GifFrame::from_rgba_speed(width as u16, height as u16, rbga_frame, 10);
@@ -235,9 +334,23 @@ impl Renderer {
             Self::insert_action(&mut tick_map, x.total_time, ActionType::Flag)
         }
 
+        for x in self.chord_data.iter() {
+            Self::insert_action(&mut tick_map, x.total_time, ActionType::Chord)
+        }
+
         tick_map
     }
 
+    /// Highlights the 3x3 area around a field for the next frame.
+    fn highlight_area(&mut self, x: i32, y: i32) {
+        for xx in (x - 1).max(0)..=(x + 1).min(self.metadata.x_size - 1) {
+            for yy in (y - 1).max(0)..=(y + 1).min(self.metadata.y_size - 1) {
+                self.game_board.changed_fields[yy as usize][xx as usize] = true;
+                self.highlighted_fields.push((xx as u32, yy as u32));
+            }
+        }
+    }
+
     fn insert_action(
         tick_map: &mut BTreeMap<i64, Vec<ActionType>>,
         total_time: i64,
@@ -347,6 +460,66 @@ impl Renderer {
             .iter_mut()
             .for_each(|row| row.iter_mut().for_each(|field| *field = false));
 
+        //Highlighted fields are drawn again in the next frame to remove the highlight
+        for (x, y) in std::mem::take(&mut self.highlighted_fields) {
+            tint_field(&mut imgbuf, x, y, [255, 235, 59], 0.35);
+            self.game_board.changed_fields[y as usize][x as usize] = true;
+        }
+
         Ok(imgbuf)
     }
 }
+
+/// Blends a 32x32 field of the image with the given color.
+fn tint_field(
+    image: &mut ImageBuffer<Rgba<u8>, Vec<u8>>,
+    x: u32,
+    y: u32,
+    color: [u8; 3],
+    strength: f32,
+) {
+    for xx in (x * 32)..(x * 32 + 32) {
+        for yy in (y * 32)..(y * 32 + 32) {
+            let pixel = image.get_pixel_mut(xx, yy);
+            let blend = |channel: u8, tint: u8| {
+                (channel as f32 * (1.0 - strength) + tint as f32 * strength) as u8
+            };
+            *pixel = Rgba([
+                blend(pixel[0], color[0]),
+                blend(pixel[1], color[1]),
+                blend(pixel[2], color[2]),
+                255,
+            ]);
+        }
+    }
+}
+
+/// Puts two rendered images next to each other, e.g. a lost game and its alternate ending.
+pub fn side_by_side(left: &[u8], right: &[u8]) -> Result<Vec<u8>, MinesweeperError> {
+    const GAP: u32 = 32;
+
+    let load = |data: &[u8]| {
+        image::load_from_memory(data)
+            .map(|image| image.to_rgba8())
+            .map_err(|_| MinesweeperError::ImageInsertion)
+    };
+    let (left, right) = (load(left)?, load(right)?);
+
+    let mut image = RgbaImage::new(
+        left.width() + GAP + right.width(),
+        left.height().max(right.height()),
+    );
+    image
+        .copy_from(&left, 0, 0)
+        .map_err(|_| MinesweeperError::ImageInsertion)?;
+    image
+        .copy_from(&right, left.width() + GAP, 0)
+        .map_err(|_| MinesweeperError::ImageInsertion)?;
+
+    let mut buffer = Cursor::new(vec![]);
+    DynamicImage::ImageRgba8(image)
+        .write_to(&mut buffer, image::ImageFormat::WebP)
+        .map_err(|_| MinesweeperError::ImageInsertion)?;
+
+    Ok(buffer.into_inner())
+}
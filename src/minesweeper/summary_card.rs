@@ -0,0 +1,104 @@
+use std::io::Cursor;
+
+use image::{DynamicImage, GenericImage, Rgba, RgbaImage, imageops};
+
+use crate::minesweeper::duration::format_duration;
+use crate::minesweeper::error::MinesweeperError;
+use crate::minesweeper::font::{draw_text, text_height, text_width};
+use crate::minesweeper::provider::provider::ApiData;
+use crate::minesweeper::renderer::Imagedata;
+use crate::minesweeper::textures::load_textures;
+
+const WIDTH: u32 = 512;
+const HEIGHT: u32 = 208;
+const HEAD_SIZE: u32 = 96;
+const MARGIN: u32 = 24;
+/// Left edge of everything right of the head
+const TEXT_X: u32 = MARGIN * 2 + HEAD_SIZE;
+
+const BACKGROUND: Rgba<u8> = Rgba([43, 45, 49, 255]);
+const TEXT: Rgba<u8> = Rgba([230, 230, 230, 255]);
+const MUTED: Rgba<u8> = Rgba([160, 160, 160, 255]);
+const WON: Rgba<u8> = Rgba([102, 187, 106, 255]);
+const LOST: Rgba<u8> = Rgba([255, 138, 101, 255]);
+
+/// Renders what the provider knows about a game without replay data: the result, time, board,
+/// flags and difficulty next to the head of the player. Missing values are left out.
+pub fn render(api_data: &ApiData, head: &RgbaImage) -> Result<Vec<u8>, MinesweeperError> {
+    let mut image = RgbaImage::from_pixel(WIDTH, HEIGHT, BACKGROUND);
+    let textures = Imagedata::new(load_textures(&false).as_slice());
+    let result_color = if api_data.won { WON } else { LOST };
+
+    for x in 0..WIDTH {
+        for y in 0..8 {
+            image.put_pixel(x, y, result_color);
+        }
+    }
+
+    let head = imageops::resize(head, HEAD_SIZE, HEAD_SIZE, imageops::FilterType::Nearest);
+    image
+        .copy_from(&head, MARGIN, MARGIN + 16)
+        .map_err(|_| MinesweeperError::ImageInsertion)?;
+
+    let mut y = MARGIN + 8;
+    let result = if api_data.won { "Won" } else { "Lost" };
+    draw_text(&mut image, TEXT_X, y, result, 4, result_color);
+    if let Some(difficulty) = &api_data.tiepe {
+        let x = TEXT_X + text_width(result, 4) + 16;
+        draw_text(&mut image, x, y + 14, difficulty, 2, MUTED);
+    }
+    y += text_height(4) + 16;
+
+    let time = format_duration(api_data.time);
+    draw_text(&mut image, TEXT_X, y, &time, 3, TEXT);
+    y += text_height(3) + 12;
+
+    let board = match (api_data.size, api_data.mines) {
+        (Some((width, height)), Some(mines)) => Some(format!("{mines} in {width}x{height}")),
+        (Some((width, height)), None) => Some(format!("{width}x{height}")),
+        (None, Some(mines)) => Some(mines.to_string()),
+        (None, None) => None,
+    };
+    if let Some(board) = board {
+        icon_row(&mut image, &textures.tnt, y, &board)?;
+        y += 40;
+    }
+
+    let flags = match (api_data.correct_flags, api_data.incorrect_flags) {
+        (Some(correct), Some(incorrect)) => Some(format!("{correct} ok {incorrect} wrong")),
+        (Some(correct), None) => Some(correct.to_string()),
+        (None, Some(incorrect)) => Some(format!("{incorrect} wrong")),
+        (None, None) => None,
+    };
+    if let Some(flags) = flags {
+        icon_row(&mut image, &textures.flag, y, &flags)?;
+    }
+
+    let mut buffer = Cursor::new(vec![]);
+    DynamicImage::ImageRgba8(image)
+        .write_to(&mut buffer, image::ImageFormat::WebP)
+        .map_err(|_| MinesweeperError::ImageInsertion)?;
+
+    Ok(buffer.into_inner())
+}
+
+/// A 32x32 texture followed by the text, vertically centered.
+fn icon_row(
+    image: &mut RgbaImage,
+    icon: &RgbaImage,
+    y: u32,
+    text: &str,
+) -> Result<(), MinesweeperError> {
+    image
+        .copy_from(icon, TEXT_X, y)
+        .map_err(|_| MinesweeperError::ImageInsertion)?;
+    draw_text(
+        image,
+        TEXT_X + 44,
+        y + (32 - text_height(3)) / 2,
+        text,
+        3,
+        TEXT,
+    );
+    Ok(())
+}
@@ -0,0 +1,313 @@
+use std::fmt::{Display, Formatter};
+
+use crate::minesweeper::generator::solver::deducible_safe_fields;
+use crate::minesweeper::minesweeper_logic::{Board, FieldState};
+use crate::minesweeper::parsers::parser::{ParsedData, TimedAction};
+use crate::minesweeper::provider::provider::ApiData;
+
+/// Two clicks of a human are at least this far apart.
+const MIN_HUMAN_INTERVAL_MS: i64 = 40;
+/// Surviving this many guesses without a loss is worth a closer look.
+const SUSPICIOUS_GUESSES: usize = 3;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Anomaly {
+    OutOfBounds {
+        x: i32,
+        y: i32,
+        total_time: i64,
+    },
+    OpenOnOpenField {
+        x: i32,
+        y: i32,
+        total_time: i64,
+    },
+    OpenOnFlaggedField {
+        x: i32,
+        y: i32,
+        total_time: i64,
+    },
+    FlagOnOpenField {
+        x: i32,
+        y: i32,
+        total_time: i64,
+    },
+    /// Actions of the same kind less than [`MIN_HUMAN_INTERVAL_MS`] apart
+    InhumanInterval {
+        x: i32,
+        y: i32,
+        interval_ms: i64,
+    },
+    /// Every guess was right, `guesses` counts the opens which did not follow from the board
+    PerfectGuesses {
+        guesses: usize,
+    },
+    ResultMismatch {
+        replay_won: bool,
+        api_won: bool,
+    },
+    FlagMismatch {
+        replay: u32,
+        api: u32,
+        correct: bool,
+    },
+    /// Columns and rows of the board
+    SizeMismatch {
+        replay: (u32, u32),
+        api: (u32, u32),
+    },
+    MineCountMismatch {
+        replay: u32,
+        api: u32,
+    },
+}
+
+/// Result of replaying a game against its board.
+pub struct Report {
+    pub anomalies: Vec<Anomaly>,
+    pub actions: usize,
+    pub guesses: usize,
+    pub won: bool,
+    pub lost: bool,
+}
+
+impl Report {
+    pub fn is_suspicious(&self) -> bool {
+        !self.anomalies.is_empty()
+    }
+}
+
+/// Replays all actions against the board and collects everything a human player could not have done.
+/// The result of the provider is compared with the replay if it is given.
+pub fn validate(data: &ParsedData, api_data: Option<&ApiData>) -> Report {
+    let mut board = data.game_board.clone();
+    let mut anomalies = Vec::new();
+    let mut guesses = 0;
+    let mut lost = false;
+    // The solver only looks at opened fields, so its result holds until more fields are opened.
+    // Safe fields stay deducible once more of the board is known.
+    let mut safe_fields: Vec<(i32, i32)> = Vec::new();
+    let mut solved_at = None;
+
+    let timeline = data.timeline();
+    for action in timeline.iter() {
+        let (x, y) = action.position();
+        let total_time = action.total_time();
+
+        let state = match board.field(x, y) {
+            Ok(field) => field.field_state.clone(),
+            Err(_) => {
+                anomalies.push(Anomaly::OutOfBounds { x, y, total_time });
+                continue;
+            }
+        };
+        match action {
+            TimedAction::Flag(flag) => {
+                if state == FieldState::Open {
+                    anomalies.push(Anomaly::FlagOnOpenField { x, y, total_time });
+                    continue;
+                }
+                flag.perform_action(&mut board);
+            }
+            TimedAction::Open(_) => {
+                match state {
+                    FieldState::Open => {
+                        anomalies.push(Anomaly::OpenOnOpenField { x, y, total_time })
+                    }
+                    FieldState::Flagged => {
+                        anomalies.push(Anomaly::OpenOnFlaggedField { x, y, total_time })
+                    }
+                    _ => {}
+                }
+
+                // The first click can never be deduced
+                if board.open_fields > 0 && state == FieldState::Closed {
+                    if !safe_fields.contains(&(x, y)) && solved_at != Some(board.open_fields) {
+                        safe_fields = deducible_safe_fields(&board);
+                        solved_at = Some(board.open_fields);
+                    }
+                    if !safe_fields.contains(&(x, y)) {
+                        guesses += 1;
+                    }
+                }
+
+                board.open_field(x as usize, y as usize);
+            }
+            TimedAction::Chord(_) => {
+                board.chord(x as usize, y as usize);
+            }
+        }
+
+        if hit_mine(&board) {
+            lost = true;
+            break;
+        }
+    }
+
+    anomalies.extend(inhuman_intervals(data));
+
+    let won = !lost && board.open_fields == board.total_fields - board.mine_count;
+    if won && guesses >= SUSPICIOUS_GUESSES {
+        anomalies.push(Anomaly::PerfectGuesses { guesses });
+    }
+
+    if let Some(api_data) = api_data {
+        anomalies.extend(compare_with_api(&board, won, api_data));
+    }
+
+    Report {
+        anomalies,
+        actions: timeline.len(),
+        guesses,
+        won,
+        lost,
+    }
+}
+
+/// `time` is relative to the previous action of the same kind and rounded down to time units,
+/// so an interval is only too short if even the end of its time unit is below the threshold.
+fn inhuman_intervals(data: &ParsedData) -> Vec<Anomaly> {
+    let timeunits = data.metadata.timeunits as i64;
+    let intervals = data
+        .open_data
+        .iter()
+        .skip(1)
+        .map(|action| (action.x, action.y, action.time))
+        .chain(
+            data.flag_data
+                .iter()
+                .skip(1)
+                .map(|action| (action.x, action.y, action.time)),
+        );
+
+    intervals
+        .filter(|(_, _, time)| (time + 1) * timeunits <= MIN_HUMAN_INTERVAL_MS)
+        .map(|(x, y, time)| Anomaly::InhumanInterval {
+            x,
+            y,
+            interval_ms: time * timeunits,
+        })
+        .collect()
+}
+
+fn compare_with_api(board: &Board, won: bool, api_data: &ApiData) -> Vec<Anomaly> {
+    let mut anomalies = Vec::new();
+
+    if won != api_data.won {
+        anomalies.push(Anomaly::ResultMismatch {
+            replay_won: won,
+            api_won: api_data.won,
+        });
+    }
+
+    let flags = board
+        .fields
+        .iter()
+        .flatten()
+        .filter(|field| field.field_state == FieldState::Flagged);
+    let correct_flags = flags.clone().filter(|field| field.mine).count() as u32;
+    let incorrect_flags = flags.filter(|field| !field.mine).count() as u32;
+
+    if let Some(api) = api_data.correct_flags.filter(|api| *api != correct_flags) {
+        anomalies.push(Anomaly::FlagMismatch {
+            replay: correct_flags,
+            api,
+            correct: true,
+        });
+    }
+    if let Some(api) = api_data
+        .incorrect_flags
+        .filter(|api| *api != incorrect_flags)
+    {
+        anomalies.push(Anomaly::FlagMismatch {
+            replay: incorrect_flags,
+            api,
+            correct: false,
+        });
+    }
+
+    let replay_size = (board.metadata.x_size as u32, board.metadata.y_size as u32);
+    if let Some(api) = api_data.size.filter(|api| *api != replay_size) {
+        anomalies.push(Anomaly::SizeMismatch {
+            replay: replay_size,
+            api,
+        });
+    }
+    if let Some(api) = api_data.mines.filter(|api| *api != board.mine_count) {
+        anomalies.push(Anomaly::MineCountMismatch {
+            replay: board.mine_count,
+            api,
+        });
+    }
+
+    anomalies
+}
+
+fn hit_mine(board: &Board) -> bool {
+    board
+        .fields
+        .iter()
+        .flatten()
+        .any(|field| field.mine && field.field_state == FieldState::Open)
+}
+
+impl Display for Anomaly {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Anomaly::OutOfBounds { x, y, total_time } => {
+                write!(
+                    f,
+                    "Action outside of the board at ({x}, {y}) at tick {total_time}"
+                )
+            }
+            Anomaly::OpenOnOpenField { x, y, total_time } => {
+                write!(f, "Opened the open field ({x}, {y}) at tick {total_time}")
+            }
+            Anomaly::OpenOnFlaggedField { x, y, total_time } => {
+                write!(
+                    f,
+                    "Opened the flagged field ({x}, {y}) at tick {total_time}"
+                )
+            }
+            Anomaly::FlagOnOpenField { x, y, total_time } => {
+                write!(f, "Flagged the open field ({x}, {y}) at tick {total_time}")
+            }
+            Anomaly::InhumanInterval { x, y, interval_ms } => {
+                write!(
+                    f,
+                    "Clicked ({x}, {y}) only {interval_ms}ms after the last click"
+                )
+            }
+            Anomaly::PerfectGuesses { guesses } => {
+                write!(f, "Won with {guesses} guesses without hitting a mine")
+            }
+            Anomaly::ResultMismatch {
+                replay_won,
+                api_won,
+            } => write!(
+                f,
+                "The replay was {} but the game is stored as {}",
+                if *replay_won { "won" } else { "not won" },
+                if *api_won { "won" } else { "not won" }
+            ),
+            Anomaly::FlagMismatch {
+                replay,
+                api,
+                correct,
+            } => write!(
+                f,
+                "The replay has {replay} {} flags but the game is stored with {api}",
+                if *correct { "correct" } else { "incorrect" }
+            ),
+            Anomaly::SizeMismatch { replay, api } => write!(
+                f,
+                "The replay is {}x{} but the game is stored as {}x{}",
+                replay.0, replay.1, api.0, api.1
+            ),
+            Anomaly::MineCountMismatch { replay, api } => write!(
+                f,
+                "The replay has {replay} mines but the game is stored with {api}"
+            ),
+        }
+    }
+}
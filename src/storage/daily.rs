@@ -0,0 +1,58 @@
+use std::cmp::Ordering;
+
+use serde::{Deserialize, Serialize};
+
+/// A finished daily puzzle, one per user, guild and day.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DailySubmission {
+    pub guild_id: u64,
+    /// Day of the puzzle as `YYYY-MM-DD` in UTC
+    pub date: String,
+    pub user_id: u64,
+    /// The game in the v3 format, older submissions use v2
+    pub replay: String,
+    pub won: bool,
+    /// Milliseconds from the first to the last action
+    pub time: i64,
+}
+
+/// A daily puzzle a user started, kept so the puzzle can not be retried after abandoning it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DailyStart {
+    pub guild_id: u64,
+    /// Day of the puzzle as `YYYY-MM-DD` in UTC
+    pub date: String,
+    pub user_id: u64,
+}
+
+pub fn find_submission<'a>(
+    submissions: &'a [DailySubmission],
+    guild_id: u64,
+    date: &str,
+    user_id: u64,
+) -> Option<&'a DailySubmission> {
+    submissions
+        .iter()
+        .find(|x| x.guild_id == guild_id && x.date == date && x.user_id == user_id)
+}
+
+/// Submissions of a guild for a day, won games first and the fastest ones on top.
+pub fn leaderboard(
+    submissions: &[DailySubmission],
+    guild_id: u64,
+    date: &str,
+) -> Vec<DailySubmission> {
+    let mut ranking: Vec<DailySubmission> = submissions
+        .iter()
+        .filter(|x| x.guild_id == guild_id && x.date == date)
+        .cloned()
+        .collect();
+
+    ranking.sort_by(|a, b| match (a.won, b.won) {
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        _ => a.time.cmp(&b.time),
+    });
+
+    ranking
+}
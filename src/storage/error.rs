@@ -0,0 +1,11 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum StorageError {
+    #[error("Unable to read the storage file")]
+    Read,
+    #[error("Unable to write the storage file")]
+    Write,
+    #[error("The storage file seems to be corrupted")]
+    Corrupted,
+}
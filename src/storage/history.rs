@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::minesweeper::provider::provider::ApiData;
+
+/// Games kept per player and provider, older ones are dropped unless they hold a best time
+const MAX_GAMES_PER_PLAYER: usize = 200;
+
+/// A game the bot has seen, either through a render or the subscription poller.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub provider: String,
+    pub game_id: String,
+    pub uuid: String,
+    /// Groups games which are comparable, e.g. the difficulty or the board size.
+    pub category: String,
+    pub time: u64,
+    pub won: bool,
+    /// Discord users who rendered this game.
+    #[serde(default)]
+    pub rendered_by: Vec<u64>,
+    /// Whether the subscription poller already checked this game for records.
+    #[serde(default)]
+    pub polled: bool,
+}
+
+impl HistoryEntry {
+    pub fn new(provider: &str, game_id: &str, api_data: &ApiData) -> HistoryEntry {
+        HistoryEntry {
+            provider: provider.to_string(),
+            game_id: game_id.to_string(),
+            uuid: api_data.uuid.clone(),
+            category: category(api_data),
+            time: api_data.time,
+            won: api_data.won,
+            rendered_by: Vec::new(),
+            polled: false,
+        }
+    }
+
+    pub(crate) fn is_game(&self, provider: &str, game_id: &str) -> bool {
+        self.provider == provider && self.game_id == game_id
+    }
+}
+
+/// Adds the entry to the history or merges it into the already known entry of the game.
+pub fn upsert(history: &mut Vec<HistoryEntry>, entry: HistoryEntry) {
+    let existing = history
+        .iter_mut()
+        .find(|x| x.is_game(&entry.provider, &entry.game_id));
+
+    let Some(existing) = existing else {
+        let (provider, uuid) = (entry.provider.clone(), entry.uuid.clone());
+        history.push(entry);
+        trim_player(history, &provider, &uuid);
+        return;
+    };
+
+    for user in entry.rendered_by {
+        if !existing.rendered_by.contains(&user) {
+            existing.rendered_by.push(user);
+        }
+    }
+    existing.polled |= entry.polled;
+}
+
+/// Drops the oldest games of the player above [`MAX_GAMES_PER_PLAYER`], keeping the fastest
+/// game of every category so records are still detected against it.
+fn trim_player(history: &mut Vec<HistoryEntry>, provider: &str, uuid: &str) {
+    let is_player = |entry: &HistoryEntry| entry.provider == provider && entry.uuid == uuid;
+    let mut excess = history
+        .iter()
+        .filter(|entry| is_player(entry))
+        .count()
+        .saturating_sub(MAX_GAMES_PER_PLAYER);
+    if excess == 0 {
+        return;
+    }
+
+    let mut best_games: HashMap<&str, &HistoryEntry> = HashMap::new();
+    for entry in history.iter().filter(|entry| is_player(entry) && entry.won) {
+        let best = best_games.entry(entry.category.as_str()).or_insert(entry);
+        if entry.time < best.time {
+            *best = entry;
+        }
+    }
+    let best_games: Vec<String> = best_games
+        .into_values()
+        .map(|entry| entry.game_id.clone())
+        .collect();
+
+    //The history is in the order games were seen, so the oldest come first
+    history.retain(|entry| {
+        let keep = excess == 0 || !is_player(entry) || best_games.contains(&entry.game_id);
+        if !keep {
+            excess -= 1;
+        }
+        keep
+    });
+}
+
+/// Fastest won game in the category, optionally limited to a single player.
+pub fn best_time<'a>(
+    history: impl Iterator<Item = &'a HistoryEntry>,
+    provider: &str,
+    category: &str,
+    uuid: Option<&str>,
+) -> Option<u64> {
+    history
+        .filter(|entry| entry.won && entry.provider == provider && entry.category == category)
+        .filter(|entry| uuid.is_none_or(|uuid| entry.uuid == uuid))
+        .map(|entry| entry.time)
+        .min()
+}
+
+/// The difficulty if the provider sends one, otherwise the board size from the game data or the provider.
+pub fn category(api_data: &ApiData) -> String {
+    api_data
+        .tiepe
+        .clone()
+        .or_else(|| {
+            api_data
+                .game_data
+                .as_ref()
+                .and_then(|game_data| game_data.split_once('='))
+                .and_then(|(_, data)| data.split_once([',', '+']))
+                .map(|(size, _)| size.to_string())
+        })
+        .or_else(|| {
+            api_data
+                .size
+                .map(|(width, height)| format!("{width}x{height}"))
+        })
+        .unwrap_or("unknown".to_string())
+}
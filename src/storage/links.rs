@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+/// A verified connection between a Discord user and a Minecraft account.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AccountLink {
+    pub discord_user: u64,
+    pub uuid: String,
+    pub name: String,
+}
+
+/// A link which waits for the player to enter the code in-game.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PendingLink {
+    pub discord_user: u64,
+    pub uuid: String,
+    pub name: String,
+    pub code: String,
+    /// Unix timestamp in seconds
+    pub created_at: i64,
+}
+
+/// UUIDs are sent with and without dashes depending on the API.
+pub fn normalize_uuid(uuid: &str) -> String {
+    uuid.replace('-', "").to_lowercase()
+}
+
+pub fn find_by_discord_user(links: &[AccountLink], discord_user: u64) -> Option<&AccountLink> {
+    links.iter().find(|x| x.discord_user == discord_user)
+}
+
+pub fn find_by_uuid<'a>(links: &'a [AccountLink], uuid: &str) -> Option<&'a AccountLink> {
+    let uuid = normalize_uuid(uuid);
+    links.iter().find(|x| normalize_uuid(&x.uuid) == uuid)
+}
@@ -0,0 +1,6 @@
+pub mod daily;
+pub mod error;
+pub mod history;
+pub mod links;
+pub mod store;
+pub mod subscriptions;
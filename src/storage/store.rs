@@ -0,0 +1,108 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serenity::client::Context;
+use serenity::prelude::TypeMapKey;
+use tokio::sync::RwLock;
+
+use crate::storage::daily::{DailyStart, DailySubmission};
+use crate::storage::error::StorageError;
+use crate::storage::history::HistoryEntry;
+use crate::storage::links::{AccountLink, PendingLink};
+use crate::storage::subscriptions::Subscription;
+
+const DEFAULT_STORAGE_PATH: &str = "data.json";
+
+/// Everything the bot persists between restarts, stored as a single JSON file.
+#[derive(Default, Serialize, Deserialize)]
+pub struct StorageData {
+    #[serde(default)]
+    pub history: Vec<HistoryEntry>,
+    #[serde(default)]
+    pub subscriptions: Vec<Subscription>,
+    /// Guilds in which posted game ids and links are rendered automatically.
+    #[serde(default)]
+    pub auto_render_guilds: Vec<u64>,
+    #[serde(default)]
+    pub links: Vec<AccountLink>,
+    #[serde(default)]
+    pub pending_links: Vec<PendingLink>,
+    #[serde(default)]
+    pub daily_submissions: Vec<DailySubmission>,
+    /// Daily puzzles of today which were started, older days are removed on the next start
+    #[serde(default)]
+    pub daily_starts: Vec<DailyStart>,
+    /// Mixed into the seeds of daily puzzles if `DAILY_SECRET` is not set, generated once
+    #[serde(default)]
+    pub daily_secret: Option<String>,
+}
+
+pub struct Storage {
+    path: PathBuf,
+    data: RwLock<StorageData>,
+}
+
+pub struct StorageKey;
+
+impl TypeMapKey for StorageKey {
+    type Value = Arc<Storage>;
+}
+
+impl Storage {
+    /// Loads the storage from the path in `STORAGE_PATH` (Default: `data.json`).
+    /// A missing file results in an empty storage which is created on the first write.
+    pub fn load() -> Result<Storage, StorageError> {
+        let path = PathBuf::from(
+            std::env::var("STORAGE_PATH").unwrap_or(DEFAULT_STORAGE_PATH.to_string()),
+        );
+
+        let data = if path.exists() {
+            let raw = std::fs::read_to_string(&path).map_err(|_| StorageError::Read)?;
+            serde_json::from_str(raw.as_str()).map_err(|_| StorageError::Corrupted)?
+        } else {
+            StorageData::default()
+        };
+
+        Ok(Storage {
+            path,
+            data: RwLock::new(data),
+        })
+    }
+
+    pub async fn read<R>(&self, f: impl FnOnce(&StorageData) -> R) -> R {
+        f(&*self.data.read().await)
+    }
+
+    /// Applies the changes of `f` and writes the whole storage back to disk.
+    /// The lock is held until the file is replaced, so writes reach the disk in order.
+    pub async fn write<R>(&self, f: impl FnOnce(&mut StorageData) -> R) -> Result<R, StorageError> {
+        let mut data = self.data.write().await;
+        let result = f(&mut data);
+
+        let raw = serde_json::to_string(&*data).map_err(|_| StorageError::Write)?;
+        let path = self.path.clone();
+        tokio::task::spawn_blocking(move || write_file(&path, raw))
+            .await
+            .map_err(|_| StorageError::Write)??;
+
+        Ok(result)
+    }
+}
+
+/// Writes to a temporary file next to the storage first, a crash while writing leaves the old file intact.
+fn write_file(path: &Path, raw: String) -> Result<(), StorageError> {
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(".tmp");
+    std::fs::write(&temp_path, raw).map_err(|_| StorageError::Write)?;
+    std::fs::rename(&temp_path, path).map_err(|_| StorageError::Write)
+}
+
+pub async fn get_storage(ctx: &Context) -> Arc<Storage> {
+    ctx.data
+        .read()
+        .await
+        .get::<StorageKey>()
+        .expect("Storage was not initialized")
+        .clone()
+}
@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Subscription {
+    pub channel_id: u64,
+    pub provider: String,
+    pub personal_bests: bool,
+}
@@ -0,0 +1,2 @@
+pub mod poller;
+pub mod records;
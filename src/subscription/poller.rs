@@ -0,0 +1,193 @@
+use std::time::Duration;
+
+use serenity::client::Context;
+use serenity::model::id::ChannelId;
+
+use crate::commands::render::{
+    create_game_embed, fetch_player_data, game_attachment, get_image_data,
+};
+use crate::minesweeper::provider::provider::{ApiData, Provider};
+use crate::minesweeper::provider::registry::{ProviderRegistry, fetch_blocking, get_registry};
+use crate::storage::history::{HistoryEntry, category, upsert};
+use crate::storage::store::get_storage;
+use crate::storage::subscriptions::Subscription;
+use crate::subscription::records::{Record, detect_record};
+
+const DEFAULT_POLL_INTERVAL: u64 = 60;
+
+pub fn start(ctx: Context) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(poll_interval());
+
+        loop {
+            interval.tick().await;
+            poll(&ctx).await;
+        }
+    });
+}
+
+fn poll_interval() -> Duration {
+    Duration::from_secs(
+        std::env::var("SUBSCRIPTION_POLL_INTERVAL")
+            .ok()
+            .and_then(|x| x.parse().ok())
+            .unwrap_or(DEFAULT_POLL_INTERVAL),
+    )
+}
+
+async fn poll(ctx: &Context) {
+    let storage = get_storage(ctx).await;
+    let subscriptions = storage.read(|data| data.subscriptions.clone()).await;
+    if subscriptions.is_empty() {
+        return;
+    }
+
+    //Games of this poll are collected and written at once, not one write per game
+    let mut history = storage.read(|data| data.history.clone()).await;
+    let mut polled_games = Vec::new();
+
+    let registry = get_registry(ctx).await;
+    for provider in registry.providers() {
+        let subscriptions: Vec<&Subscription> = subscriptions
+            .iter()
+            .filter(|x| x.provider == provider.id())
+            .collect();
+
+        if subscriptions.is_empty() || !provider.supports_recent_games() {
+            continue;
+        }
+
+        let fetched = fetch_blocking(ctx, provider, |provider| provider.fetch_recent_games()).await;
+        let game_ids = match fetched {
+            Ok(game_ids) => game_ids,
+            Err(error) => {
+                println!(
+                    "Unable to fetch recent games from {}: {}",
+                    provider.name(),
+                    error
+                );
+                continue;
+            }
+        };
+
+        //Oldest game first, so records are checked in the order they were played
+        for game_id in game_ids.iter().rev() {
+            let known = history
+                .iter()
+                .any(|x| x.polled && x.is_game(provider.id(), game_id));
+
+            if known {
+                continue;
+            }
+
+            let fetch_id = game_id.clone();
+            let fetched = fetch_blocking(ctx, provider, move |provider| {
+                provider.fetch_data(fetch_id.as_str())
+            })
+            .await;
+            let Ok(api_data) = fetched else {
+                continue;
+            };
+
+            let mut entry = HistoryEntry::new(provider.id(), game_id, &api_data);
+            entry.polled = true;
+
+            let record = detect_record(&history, &entry);
+            upsert(&mut history, entry.clone());
+            polled_games.push(entry);
+
+            if let Some(record) = record {
+                post_record(
+                    ctx,
+                    &registry,
+                    provider,
+                    game_id,
+                    &api_data,
+                    record,
+                    &subscriptions,
+                )
+                .await;
+            }
+        }
+    }
+
+    if polled_games.is_empty() {
+        return;
+    }
+
+    //Merged into the stored history, renders might have added games in the meantime
+    let result = storage
+        .write(|data| {
+            for entry in polled_games {
+                upsert(&mut data.history, entry);
+            }
+        })
+        .await;
+    if let Err(error) = result {
+        println!("Unable to save polled games: {error}");
+    }
+}
+
+async fn post_record(
+    ctx: &Context,
+    registry: &ProviderRegistry,
+    provider: &dyn Provider,
+    game_id: &str,
+    api_data: &ApiData,
+    record: Record,
+    subscriptions: &[&Subscription],
+) {
+    let player_data = fetch_player_data(ctx, provider, api_data).await;
+
+    let image_data = get_image_data(api_data, Some(&player_data), &false, &false)
+        .await
+        .ok();
+
+    let content = match record {
+        Record::TopTime => format!(
+            "New top time in **{}** by **{}**!",
+            category(api_data),
+            player_data.name
+        ),
+        Record::PersonalBest => format!(
+            "New personal best in **{}** by **{}**!",
+            category(api_data),
+            player_data.name
+        ),
+    };
+
+    for subscription in subscriptions {
+        if matches!(record, Record::PersonalBest) && !subscription.personal_bests {
+            continue;
+        }
+
+        let result = ChannelId(subscription.channel_id)
+            .send_message(&ctx.http, |message| {
+                message.content(&content).embed(|e| {
+                    create_game_embed(
+                        e,
+                        registry,
+                        provider,
+                        game_id,
+                        api_data,
+                        &player_data,
+                        image_data.as_ref(),
+                    )
+                });
+
+                if let Some(data) = &image_data {
+                    message.add_file(game_attachment(data, false));
+                }
+
+                message
+            })
+            .await;
+
+        if let Err(error) = result {
+            println!(
+                "Was unable to post record to channel {}! {:?}",
+                subscription.channel_id, error
+            )
+        }
+    }
+}
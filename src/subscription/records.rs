@@ -0,0 +1,38 @@
+use crate::storage::history::{HistoryEntry, best_time};
+
+pub enum Record {
+    TopTime,
+    PersonalBest,
+}
+
+/// Only games beating an already known time count as a record, so the first games seen
+/// after starting with an empty history don't flood the subscribed channels.
+pub fn detect_record(history: &[HistoryEntry], entry: &HistoryEntry) -> Option<Record> {
+    if !entry.won {
+        return None;
+    }
+
+    //The game itself might already be known from a render
+    let previous_games = || {
+        history
+            .iter()
+            .filter(|x| !x.is_game(&entry.provider, &entry.game_id))
+    };
+
+    let top_time = best_time(previous_games(), &entry.provider, &entry.category, None);
+    if top_time.is_some_and(|best| entry.time < best) {
+        return Some(Record::TopTime);
+    }
+
+    let personal_best = best_time(
+        previous_games(),
+        &entry.provider,
+        &entry.category,
+        Some(entry.uuid.as_str()),
+    );
+    if personal_best.is_some_and(|best| entry.time < best) {
+        return Some(Record::PersonalBest);
+    }
+
+    None
+}